@@ -0,0 +1,1448 @@
+use crate::{
+    checker::inference::{Scheme, Substitution},
+    interpreter::{FloatType, IntegerType, Type},
+    parser::{
+        expression::{
+            binary::{self, Binary},
+            unary::{self, Unary},
+        },
+        interpolation::Segment,
+        let_in::Assignment,
+        prelude::*,
+        types,
+    },
+};
+
+mod inference;
+
+/// A type error, along with where in the source it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub line: u32,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl Error {
+    fn new(span: &Span, kind: ErrorKind) -> Self {
+        Self {
+            line: span.location_line(),
+            column: span.get_utf8_column(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    UnboundIdentifier {
+        name: String,
+    },
+    Uninitialized {
+        name: String,
+    },
+    UnknownType {
+        name: String,
+    },
+    TypeMismatch {
+        expected: Type,
+        found: Type,
+    },
+    BinaryOperation {
+        operator: binary::Operator,
+        lhs_type: Type,
+        rhs_type: Type,
+    },
+    UnaryOperation {
+        data_type: Type,
+        detail: String,
+    },
+    ArgumentCount {
+        expected: usize,
+        found: usize,
+    },
+    Interpolation {
+        data_type: Type,
+    },
+}
+
+impl ErrorKind {
+    /// The types the error refers to
+    fn types_mut(&mut self) -> Vec<&mut Type> {
+        match self {
+            Self::TypeMismatch { expected, found } => vec![expected, found],
+            Self::BinaryOperation {
+                lhs_type, rhs_type, ..
+            } => vec![lhs_type, rhs_type],
+            Self::UnaryOperation { data_type, .. } | Self::Interpolation { data_type } => {
+                vec![data_type]
+            }
+            Self::UnboundIdentifier { .. }
+            | Self::Uninitialized { .. }
+            | Self::UnknownType { .. }
+            | Self::ArgumentCount { .. } => Vec::new(),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::UnboundIdentifier { name } => write!(f, "Unbound identifier `{name}`"),
+            ErrorKind::Uninitialized { name } => {
+                write!(f, "`{name}` is used before it is initialized")
+            }
+            ErrorKind::UnknownType { name } => write!(f, "Unknown type `{name}`"),
+            ErrorKind::TypeMismatch { expected, found } => {
+                write!(f, "Expected {expected}, found {found}")
+            }
+            ErrorKind::BinaryOperation {
+                operator,
+                lhs_type,
+                rhs_type,
+            } => write!(
+                f,
+                "`{}` is not defined for {lhs_type} and {rhs_type}",
+                operator.symbol()
+            ),
+            ErrorKind::UnaryOperation { data_type, detail } => {
+                write!(f, "Cannot perform unary operation on {data_type}: {detail}")
+            }
+            ErrorKind::ArgumentCount { expected, found } => {
+                write!(f, "Expected {expected} arguments, found {found}")
+            }
+            ErrorKind::Interpolation { data_type } => {
+                write!(f, "Cannot interpolate {data_type} into a string")
+            }
+        }
+    }
+}
+
+/// Infers the type of an expression without evaluating it, returning its type or every type
+/// error found. Functions bound with `let` are generic over any types they do not constrain.
+pub fn check(expression: &Expression) -> Result<Type, Vec<Error>> {
+    let mut checker = Checker::default();
+    let data_type = checker.expression(expression);
+
+    for deferred in std::mem::take(&mut checker.deferred) {
+        checker.deferred_check(&deferred);
+    }
+
+    let mut errors = checker.errors;
+    for error in &mut errors {
+        let mut types = error.kind.types_mut();
+        for data_type in &mut types {
+            **data_type = checker.types.resolve(data_type);
+        }
+        inference::rename_variables(types);
+    }
+
+    match data_type {
+        Some(data_type) if errors.is_empty() => {
+            let mut data_type = checker.types.resolve(&data_type);
+            inference::rename_variables([&mut data_type]);
+            Ok(data_type)
+        }
+        _ => Err(errors),
+    }
+}
+
+/// What is known about a variable while checking
+#[derive(Debug, Clone)]
+enum Variable {
+    Typed(Scheme),
+    /// The variable's type could not be determined, which has already been reported
+    Unknown,
+    /// A variable in a `let rec` whose value has not yet been checked
+    Uninitialized,
+}
+
+impl From<Option<Type>> for Variable {
+    fn from(data_type: Option<Type>) -> Self {
+        data_type.map_or(Self::Unknown, |data_type| Self::Typed(data_type.into()))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Checker<'a> {
+    /// The variables in scope, with inner bindings after the bindings they shadow
+    variables: Vec<(&'a str, Variable)>,
+    /// What has been inferred about type variables so far
+    types: Substitution,
+    /// Checks to make once inference is finished, including copies for each use of a generic
+    /// function whose generic variables they depend on
+    deferred: Vec<Deferred<'a>>,
+    errors: Vec<Error>,
+}
+
+/// A check on types that may only be known once the code after them has been checked
+#[derive(Debug, Clone)]
+struct Deferred<'a> {
+    span: Span<'a>,
+    types: Vec<Type>,
+    check: Check,
+}
+
+#[derive(Debug, Clone)]
+enum Check {
+    /// The type of an expression embedded in an interpolated string, which can be any type but
+    /// a function's, as functions have no text to convert to
+    Interpolated,
+    /// The operand of a unary operator that is defined for every numeric type
+    Numeric { detail: &'static str },
+    /// The operands of a binary operator that is defined for several types
+    Binary(binary::Operator),
+}
+
+/// Each method returns the type of what it checked, or `None` if it could not be determined
+/// because of an error. Errors are only reported where they originate, so that one mistake
+/// does not cause errors in everything that depends on it.
+impl<'a> Checker<'a> {
+    fn expression(&mut self, expression: &Expression<'a>) -> Option<Type> {
+        match expression {
+            Expression::Literal(literal) => Some(match literal {
+                Literal::Integer(integer) => Type::from(integer),
+                Literal::Float(float) => float
+                    .suffix
+                    .map_or(Type::Float(FloatType::Float64), Type::from),
+                Literal::Character(_) => Type::Character,
+                Literal::String(_) => Type::String,
+                Literal::Boolean(_) => Type::Boolean,
+            }),
+            Expression::Binary(binary) => self.binary(binary),
+            Expression::Unary(unary) => self.unary(unary),
+            Expression::Identifier(identifier) => self.identifier(identifier),
+            Expression::LetIn(let_in) => {
+                let scope = self.variables.len();
+                if let_in.recursive {
+                    self.recursive_assignments(&let_in.assignments);
+                } else {
+                    for assignment in &let_in.assignments {
+                        let variable = match self.expression(&assignment.value) {
+                            Some(data_type) => {
+                                Variable::Typed(self.generalize(&data_type, self.variables.len()))
+                            }
+                            None => Variable::Unknown,
+                        };
+                        self.variables
+                            .push((assignment.identifier.0.fragment(), variable));
+                    }
+                }
+                let data_type = self.expression(&let_in.expression);
+                self.variables.truncate(scope);
+                data_type
+            }
+            Expression::Conditional(conditional) => self.conditional(conditional),
+            Expression::Function(function) => self.function(function),
+            Expression::Interpolation(interpolation) => {
+                for segment in &interpolation.segments {
+                    if let Segment::Expression { expression, span } = segment {
+                        if let Some(data_type) = self.expression(expression) {
+                            self.defer(span, vec![data_type], Check::Interpolated);
+                        }
+                    }
+                }
+                Some(Type::String)
+            }
+        }
+    }
+
+    /// The condition has to be a `Boolean`, and both branches have to have the same type, which
+    /// is the type of the conditional. Mismatches are reported at the `if`.
+    fn conditional(&mut self, conditional: &Conditional<'a>) -> Option<Type> {
+        if let Some(condition) = self.expression(&conditional.condition) {
+            self.unify(&conditional.span, &Type::Boolean, &condition);
+        }
+        let consequent = self.expression(&conditional.consequent);
+        let alternative = self.expression(&conditional.alternative);
+        self.unify(&conditional.span, &consequent?, &alternative?)
+    }
+
+    fn defer(&mut self, span: &Span<'a>, types: Vec<Type>, check: Check) {
+        self.deferred.push(Deferred {
+            span: *span,
+            types,
+            check,
+        });
+    }
+
+    /// Makes a deferred check, unless a type it depends on is still unknown. An error is only
+    /// reported once where it is found, though a generic function may fail the check for each
+    /// type it is used with.
+    fn deferred_check(&mut self, deferred: &Deferred) {
+        let types: Vec<_> = deferred
+            .types
+            .iter()
+            .map(|data_type| self.types.resolve(data_type))
+            .collect();
+        if types
+            .iter()
+            .any(|data_type| matches!(data_type, Type::Variable(_)))
+        {
+            return;
+        }
+
+        let kind = match (&deferred.check, types.as_slice()) {
+            (Check::Interpolated, [data_type @ Type::Function { .. }]) => {
+                ErrorKind::Interpolation {
+                    data_type: data_type.clone(),
+                }
+            }
+            (Check::Numeric { detail }, [data_type]) if numeric(data_type, data_type).is_none() => {
+                ErrorKind::UnaryOperation {
+                    data_type: data_type.clone(),
+                    detail: (*detail).to_string(),
+                }
+            }
+            (Check::Binary(operator), [left, right])
+                if self.binary_type(operator, left, right).is_none() =>
+            {
+                ErrorKind::BinaryOperation {
+                    operator: operator.clone(),
+                    lhs_type: left.clone(),
+                    rhs_type: right.clone(),
+                }
+            }
+            _ => return,
+        };
+
+        let error = Error::new(&deferred.span, kind);
+        if !self.errors.iter().any(|reported| {
+            (reported.line, reported.column) == (error.line, error.column)
+                && std::mem::discriminant(&reported.kind) == std::mem::discriminant(&error.kind)
+        }) {
+            self.errors.push(error);
+        }
+    }
+
+    /// A type for one use of a variable. If it is generic, any deferred checks that depend on
+    /// its generic variables are copied for this use, so that they are made against the types
+    /// it is used with.
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let generic: Vec<_> = self
+            .deferred
+            .iter()
+            .filter(|deferred| {
+                deferred.types.iter().any(|data_type| {
+                    inference::free_variables(&self.types.resolve(data_type))
+                        .iter()
+                        .any(|variable| scheme.variables.contains(variable))
+                })
+            })
+            .cloned()
+            .collect();
+        let related: Vec<_> = generic
+            .iter()
+            .flat_map(|deferred| deferred.types.iter().cloned())
+            .collect();
+
+        let (data_type, related) = self.types.instantiate(scheme, &related);
+        let mut related = related.into_iter();
+        for mut deferred in generic {
+            deferred.types = related.by_ref().take(deferred.types.len()).collect();
+            self.deferred.push(deferred);
+        }
+        data_type
+    }
+
+    fn identifier(&mut self, identifier: &Identifier<'a>) -> Option<Type> {
+        let name = identifier.0.fragment();
+        let variable = self
+            .variables
+            .iter()
+            .rev()
+            .find(|(variable, _)| variable == name)
+            .map(|(_, variable)| variable.clone());
+
+        match variable {
+            Some(Variable::Typed(scheme)) => Some(self.instantiate(&scheme)),
+            Some(Variable::Unknown) => None,
+            Some(Variable::Uninitialized) => self.error(
+                &identifier.0,
+                ErrorKind::Uninitialized {
+                    name: (*name).to_string(),
+                },
+            ),
+            None => self.error(
+                &identifier.0,
+                ErrorKind::UnboundIdentifier {
+                    name: (*name).to_string(),
+                },
+            ),
+        }
+    }
+
+    /// Assignments in a `let rec` are checked in the order they are evaluated, so a value that
+    /// uses a later binding is an error. Function bodies are only checked once every binding
+    /// has been assigned, as they are not evaluated until they are called, and the bindings
+    /// are only made generic once all of them have been checked.
+    fn recursive_assignments(&mut self, assignments: &[Assignment<'a>]) {
+        let scope = self.variables.len();
+        self.variables.extend(
+            assignments
+                .iter()
+                .map(|assignment| (*assignment.identifier.0.fragment(), Variable::Uninitialized)),
+        );
+
+        let mut types = Vec::new();
+        for (index, assignment) in assignments.iter().enumerate() {
+            let data_type = match &assignment.value {
+                Expression::Function(_) => Some(self.types.fresh()),
+                value => self.expression(value),
+            };
+            self.variables[scope + index].1 = data_type.clone().into();
+            types.push(data_type);
+        }
+
+        for (assignment, data_type) in assignments.iter().zip(&types) {
+            if let (Expression::Function(function), Some(data_type)) =
+                (&assignment.value, data_type)
+            {
+                if let Some(function_type) = self.function(function) {
+                    self.unify(&function.span, data_type, &function_type);
+                }
+            }
+        }
+
+        for (index, data_type) in types.into_iter().enumerate() {
+            if let Some(data_type) = data_type {
+                self.variables[scope + index].1 =
+                    Variable::Typed(self.generalize(&data_type, scope));
+            }
+        }
+    }
+
+    /// Infers a function's type from its annotations and body, where parameters and return
+    /// types that are not annotated start out as type variables
+    fn function(&mut self, function: &Function<'a>) -> Option<Type> {
+        let parameters: Vec<_> = function
+            .parameters
+            .0
+            .iter()
+            .map(|(annotation, _)| self.annotation_or_fresh(annotation.as_ref()))
+            .collect();
+        let return_type = self.annotation_or_fresh(function.return_type.as_ref());
+
+        let scope = self.variables.len();
+        for ((_, identifier), data_type) in function.parameters.0.iter().zip(&parameters) {
+            self.variables
+                .push((identifier.0.fragment(), data_type.clone().into()));
+        }
+        let body_type = self.expression(&function.body);
+        self.variables.truncate(scope);
+
+        let return_type = self.unify(&function.span, &return_type?, &body_type?)?;
+        Some(Type::Function {
+            return_type: Box::new(return_type),
+            parameters: parameters.into_iter().collect::<Option<_>>()?,
+        })
+    }
+
+    fn annotation_or_fresh(&mut self, annotation: Option<&types::Type>) -> Option<Type> {
+        match annotation {
+            Some(annotation) => self.annotation(annotation),
+            None => Some(self.types.fresh()),
+        }
+    }
+
+    /// Resolves a type annotation, reporting every unknown type name in it
+    fn annotation(&mut self, annotation: &types::Type) -> Option<Type> {
+        Type::from_annotation(annotation, &mut |name| {
+            self.error::<()>(
+                name,
+                ErrorKind::UnknownType {
+                    name: name.to_string(),
+                },
+            );
+        })
+    }
+
+    fn binary(&mut self, binary: &Binary<'a>) -> Option<Type> {
+        use binary::Operator;
+
+        let left = self.expression(&binary.left);
+        let right = self.expression(&binary.right);
+        let (left, right) = (left?, right?);
+
+        if let Operator::And | Operator::Or = &binary.operator {
+            self.unify(&binary.span, &Type::Boolean, &left);
+            self.unify(&binary.span, &Type::Boolean, &right);
+            return Some(Type::Boolean);
+        }
+        let (left, right) = self.assume_operand_types(&left, &right);
+
+        // If neither operand's type is known, they have the same type, which is checked once
+        // inference is finished. Equality is defined for every type, so it needs no check.
+        if matches!((&left, &right), (Type::Variable(_), Type::Variable(_)))
+            && !matches!(binary.operator, Operator::Equal | Operator::NotEqual)
+        {
+            self.defer(
+                &binary.span,
+                vec![left.clone(), right],
+                Check::Binary(binary.operator.clone()),
+            );
+            return Some(match &binary.operator {
+                Operator::Add
+                | Operator::Subtract
+                | Operator::Multiply
+                | Operator::Divide
+                | Operator::Exponent
+                | Operator::Modulo
+                | Operator::Xor => left,
+                _ => Type::Boolean,
+            });
+        }
+
+        self.binary_type(&binary.operator, &left, &right)
+            .or_else(|| {
+                self.error(
+                    &binary.span,
+                    ErrorKind::BinaryOperation {
+                        operator: binary.operator.clone(),
+                        lhs_type: left,
+                        rhs_type: right,
+                    },
+                )
+            })
+    }
+
+    /// The type of a binary operation on operands of known types, or `None` if the operator is
+    /// not defined for them
+    fn binary_type(
+        &mut self,
+        operator: &binary::Operator,
+        left: &Type,
+        right: &Type,
+    ) -> Option<Type> {
+        use binary::Operator;
+
+        match operator {
+            Operator::And | Operator::Or => unreachable!("logical operators are checked apart"),
+            Operator::Add => match (left, right) {
+                (Type::String, Type::String | Type::Character)
+                | (Type::Character, Type::String) => Some(Type::String),
+                _ => numeric(left, right),
+            },
+            Operator::Subtract
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::Exponent
+            | Operator::Modulo => numeric(left, right),
+            Operator::Xor => match (left, right) {
+                (Type::Boolean, Type::Boolean) => Some(Type::Boolean),
+                (Type::Integer(left_type), Type::Integer(right_type))
+                    if left_type == right_type =>
+                {
+                    Some(left.clone())
+                }
+                _ => None,
+            },
+            Operator::Equal | Operator::NotEqual => (numeric(left, right).is_some()
+                || self.types.unify(left, right))
+            .then_some(Type::Boolean),
+            Operator::LessThan
+            | Operator::LessThanOrEqual
+            | Operator::GreaterThan
+            | Operator::GreaterThanOrEqual => match (left, right) {
+                (Type::Character, Type::Character) | (Type::String, Type::String) => {
+                    Some(Type::Boolean)
+                }
+                _ => numeric(left, right).map(|_| Type::Boolean),
+            },
+        }
+    }
+
+    /// Operators are defined for several types, so an operand whose type is not yet known is
+    /// assumed to have the type of the other operand
+    fn assume_operand_types(&mut self, left: &Type, right: &Type) -> (Type, Type) {
+        if let (Type::Variable(_), _) | (_, Type::Variable(_)) =
+            (self.types.resolve(left), self.types.resolve(right))
+        {
+            self.types.unify(left, right);
+        }
+        (self.types.resolve(left), self.types.resolve(right))
+    }
+
+    fn unary(&mut self, unary: &Unary<'a>) -> Option<Type> {
+        use unary::Operator;
+
+        let Some(operand) = self.expression(&unary.expression) else {
+            self.operator_expressions(&unary.operator);
+            return None;
+        };
+
+        // An operand whose type is not yet known is assumed to have the type the operator can
+        // be applied to. Any numeric type can be negated or converted, so those operands are
+        // checked once inference is finished.
+        if let Type::Variable(_) = self.types.resolve(&operand) {
+            let assumed = match &unary.operator {
+                Operator::Negate | Operator::Convert(_) => {
+                    let detail = unsupported(&unary.operator);
+                    self.defer(
+                        &unary.span,
+                        vec![operand.clone()],
+                        Check::Numeric { detail },
+                    );
+                    None
+                }
+                Operator::Not => Some(Type::Boolean),
+                Operator::Access(_) => Some(Type::String),
+                Operator::Call(arguments) => Some(Type::Function {
+                    return_type: Box::new(self.types.fresh()),
+                    parameters: arguments.iter().map(|_| self.types.fresh()).collect(),
+                }),
+            };
+            if let Some(assumed) = assumed {
+                self.types.unify(&operand, &assumed);
+            }
+        }
+
+        let detail = match (&unary.operator, self.types.resolve(&operand)) {
+            (
+                Operator::Negate,
+                data_type @ (Type::Integer(_)
+                | Type::Float(_)
+                | Type::BigInt
+                | Type::Rational
+                | Type::Variable(_)),
+            ) => return Some(data_type),
+            (Operator::Not, Type::Boolean) => return Some(Type::Boolean),
+            (Operator::Access(index), Type::String) => {
+                let index = self.expression(index.as_ref())?;
+                // Strings can be indexed by any integer type
+                if let Type::Integer(_) = self.types.resolve(&index) {
+                    return Some(Type::Character);
+                }
+                return self
+                    .unify(&unary.span, &Type::Integer(IntegerType::Int64), &index)
+                    .map(|_| Type::Character);
+            }
+            (
+                Operator::Convert(data_type),
+                Type::Integer(_)
+                | Type::Float(_)
+                | Type::BigInt
+                | Type::Rational
+                | Type::Variable(_),
+            ) => match self.annotation(data_type)? {
+                data_type @ (Type::Integer(_) | Type::Float(_) | Type::BigInt | Type::Rational) => {
+                    return Some(data_type)
+                }
+                target => (
+                    self.types.resolve(&operand),
+                    format!("numbers cannot be converted to {target}"),
+                ),
+            },
+            (
+                Operator::Call(arguments),
+                Type::Function {
+                    return_type,
+                    parameters,
+                },
+            ) => {
+                self.call(&unary.span, arguments, &parameters);
+                return Some(*return_type);
+            }
+            (operator, data_type) => {
+                self.operator_expressions(operator);
+                (data_type, unsupported(operator).to_string())
+            }
+        };
+
+        self.error(
+            &unary.span,
+            ErrorKind::UnaryOperation {
+                data_type: detail.0,
+                detail: detail.1,
+            },
+        )
+    }
+
+    /// Checks the arguments of a call against the parameters of the function called
+    fn call(&mut self, span: &Span, arguments: &[Expression<'a>], parameters: &[Type]) {
+        let arguments: Vec<_> = arguments
+            .iter()
+            .map(|argument| self.expression(argument))
+            .collect();
+
+        if arguments.len() == parameters.len() {
+            for (parameter, argument) in parameters.iter().zip(arguments) {
+                if let Some(argument) = argument {
+                    self.unify(span, parameter, &argument);
+                }
+            }
+        } else {
+            self.error::<()>(
+                span,
+                ErrorKind::ArgumentCount {
+                    expected: parameters.len(),
+                    found: arguments.len(),
+                },
+            );
+        }
+    }
+
+    /// Checks the arguments, index or type that are part of a unary operator that cannot be
+    /// applied, so that errors in them are still reported
+    fn operator_expressions(&mut self, operator: &unary::Operator<'a>) {
+        use unary::Operator;
+
+        match operator {
+            Operator::Call(arguments) => {
+                for argument in arguments {
+                    self.expression(argument);
+                }
+            }
+            Operator::Access(index) => {
+                self.expression(index);
+            }
+            Operator::Convert(data_type) => {
+                self.annotation(data_type);
+            }
+            Operator::Negate | Operator::Not => {}
+        }
+    }
+
+    /// Makes a type generic over the type variables not used by the first `scope` variables
+    fn generalize(&self, data_type: &Type, scope: usize) -> Scheme {
+        self.types.generalize(
+            data_type,
+            self.variables[..scope]
+                .iter()
+                .filter_map(|(_, variable)| match variable {
+                    Variable::Typed(scheme) => Some(scheme),
+                    Variable::Unknown | Variable::Uninitialized => None,
+                }),
+        )
+    }
+
+    /// Unifies the type found with the type expected, reporting a mismatch at `span`
+    fn unify(&mut self, span: &Span, expected: &Type, found: &Type) -> Option<Type> {
+        if self.types.unify(expected, found) {
+            Some(self.types.resolve(expected))
+        } else {
+            self.error(
+                span,
+                ErrorKind::TypeMismatch {
+                    expected: expected.clone(),
+                    found: found.clone(),
+                },
+            )
+        }
+    }
+
+    /// Records an error, returning `None` so it can be used as the type of whatever failed
+    fn error<T>(&mut self, span: &Span, kind: ErrorKind) -> Option<T> {
+        self.errors.push(Error::new(span, kind));
+        None
+    }
+}
+
+/// The type of arithmetic on two numbers. Numbers of the same type keep that type, an integer
+/// paired with a `BigInt` is promoted to `BigInt`, an integer or `BigInt` paired with a
+/// `Rational` is promoted to `Rational`, and any of them paired with a float is promoted to the
+/// float's type.
+fn numeric(left: &Type, right: &Type) -> Option<Type> {
+    match (left, right) {
+        (Type::Integer(left_type), Type::Integer(right_type)) if left_type == right_type => {
+            Some(left.clone())
+        }
+        (Type::Float(left_type), Type::Float(right_type)) if left_type == right_type => {
+            Some(left.clone())
+        }
+        (Type::Integer(_) | Type::BigInt, Type::BigInt) | (Type::BigInt, Type::Integer(_)) => {
+            Some(Type::BigInt)
+        }
+        (Type::Integer(_) | Type::BigInt | Type::Rational, Type::Rational)
+        | (Type::Rational, Type::Integer(_) | Type::BigInt) => Some(Type::Rational),
+        (Type::Integer(_) | Type::BigInt | Type::Rational, data_type @ Type::Float(_))
+        | (data_type @ Type::Float(_), Type::Integer(_) | Type::BigInt | Type::Rational) => {
+            Some(data_type.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Why a unary operator cannot be applied to an operand
+const fn unsupported(operator: &unary::Operator) -> &'static str {
+    use unary::Operator;
+
+    match operator {
+        Operator::Negate => "only numbers can be negated",
+        Operator::Not => "only booleans can be inverted",
+        Operator::Access(_) => "only strings can be indexed",
+        Operator::Call(_) => "only functions can be called",
+        Operator::Convert(_) => "only numbers can be converted",
+    }
+}
+
+#[cfg(test)]
+fn check_source(source: &str) -> Result<Type, Vec<Error>> {
+    let (input, expression) = Expression::parse(source.into()).unwrap();
+    assert!(input.is_empty(), "unparsed input: {input}");
+    check(&expression)
+}
+
+#[test]
+fn well_typed_expressions_check() {
+    assert_eq!(
+        check_source("1 + 2 * 3"),
+        Ok(Type::Integer(IntegerType::Int64))
+    );
+    assert_eq!(check_source("1 + 2.5"), Ok(Type::Float(FloatType::Float64)));
+    assert_eq!(check_source("\"a\" + 'b'"), Ok(Type::String));
+    assert_eq!(check_source("1 < 2.0 && !false"), Ok(Type::Boolean));
+    assert_eq!(check_source("\"abc\"[1]"), Ok(Type::Character));
+    assert_eq!(
+        check_source("let add = fn(Int64 x, Int64 y) -> Int64 { x + y } in add(1, 2)"),
+        Ok(Type::Integer(IntegerType::Int64))
+    );
+    assert_eq!(
+        check_source("fn(fn(Int64) -> Boolean f) -> Boolean { f(1) }"),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Boolean),
+            parameters: vec![Type::Function {
+                return_type: Box::new(Type::Boolean),
+                parameters: vec![Type::Integer(IntegerType::Int64)]
+            }]
+        })
+    );
+    assert_eq!(
+        check_source("fn([(Int64, Character)] pairs) -> [(Int64, Character)] { pairs }"),
+        Ok(Type::Function {
+            return_type: Box::new(Type::List(Box::new(Type::Tuple(vec![
+                Type::Integer(IntegerType::Int64),
+                Type::Character
+            ])))),
+            parameters: vec![Type::List(Box::new(Type::Tuple(vec![
+                Type::Integer(IntegerType::Int64),
+                Type::Character
+            ])))]
+        })
+    );
+    assert_eq!(
+        check_source(
+            "let rec
+                even = fn(Int64 n) -> Boolean { n == 0 || odd(n - 1) },
+                odd = fn(Int64 n) -> Boolean { n != 0 && even(n - 1) },
+            in even(10)"
+        ),
+        Ok(Type::Boolean)
+    );
+}
+
+#[test]
+fn type_errors_are_reported() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("1 + true"),
+        Err(vec![error(
+            1,
+            3,
+            ErrorKind::BinaryOperation {
+                operator: binary::Operator::Add,
+                lhs_type: Type::Integer(IntegerType::Int64),
+                rhs_type: Type::Boolean
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("-'a' + y"),
+        Err(vec![
+            error(
+                1,
+                1,
+                ErrorKind::UnaryOperation {
+                    data_type: Type::Character,
+                    detail: "only numbers can be negated".to_string()
+                }
+            ),
+            error(
+                1,
+                8,
+                ErrorKind::UnboundIdentifier {
+                    name: "y".to_string()
+                }
+            )
+        ])
+    );
+    assert_eq!(
+        check_source("fn(Int x) -> Boolean { 1 }"),
+        Err(vec![
+            error(
+                1,
+                4,
+                ErrorKind::UnknownType {
+                    name: "Int".to_string()
+                }
+            ),
+            error(
+                1,
+                1,
+                ErrorKind::TypeMismatch {
+                    expected: Type::Boolean,
+                    found: Type::Integer(IntegerType::Int64)
+                }
+            )
+        ])
+    );
+    assert_eq!(
+        check_source("fn(Int64 x) -> Boolean { x }"),
+        Err(vec![error(
+            1,
+            1,
+            ErrorKind::TypeMismatch {
+                expected: Type::Boolean,
+                found: Type::Integer(IntegerType::Int64)
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("let f = fn(Int64 x) -> Int64 { x } in f(1.0) + f(1, 2)"),
+        Err(vec![
+            error(
+                1,
+                40,
+                ErrorKind::TypeMismatch {
+                    expected: Type::Integer(IntegerType::Int64),
+                    found: Type::Float(FloatType::Float64)
+                }
+            ),
+            error(
+                1,
+                49,
+                ErrorKind::ArgumentCount {
+                    expected: 1,
+                    found: 2
+                }
+            )
+        ])
+    );
+    assert_eq!(
+        check_source("let rec x = y, y = 1 in x"),
+        Err(vec![error(
+            1,
+            13,
+            ErrorKind::Uninitialized {
+                name: "y".to_string()
+            }
+        )])
+    );
+}
+
+#[test]
+fn operator_arguments_are_checked_after_errors() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("undefined(missing, 1 + true)"),
+        Err(vec![
+            error(
+                1,
+                1,
+                ErrorKind::UnboundIdentifier {
+                    name: "undefined".to_string()
+                }
+            ),
+            error(
+                1,
+                11,
+                ErrorKind::UnboundIdentifier {
+                    name: "missing".to_string()
+                }
+            ),
+            error(
+                1,
+                22,
+                ErrorKind::BinaryOperation {
+                    operator: binary::Operator::Add,
+                    lhs_type: Type::Integer(IntegerType::Int64),
+                    rhs_type: Type::Boolean
+                }
+            )
+        ])
+    );
+    assert_eq!(
+        check_source("true[missing]"),
+        Err(vec![
+            error(
+                1,
+                6,
+                ErrorKind::UnboundIdentifier {
+                    name: "missing".to_string()
+                }
+            ),
+            error(
+                1,
+                5,
+                ErrorKind::UnaryOperation {
+                    data_type: Type::Boolean,
+                    detail: "only strings can be indexed".to_string()
+                }
+            )
+        ])
+    );
+}
+
+#[test]
+fn compound_type_errors_are_reported() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("fn([Int64] xs, Option<Strin> x) -> (Int64,) { xs }"),
+        Err(vec![
+            error(
+                1,
+                23,
+                ErrorKind::UnknownType {
+                    name: "Strin".to_string()
+                }
+            ),
+            error(
+                1,
+                16,
+                ErrorKind::UnknownType {
+                    name: "Option".to_string()
+                }
+            ),
+            error(
+                1,
+                1,
+                ErrorKind::TypeMismatch {
+                    expected: Type::Tuple(vec![Type::Integer(IntegerType::Int64)]),
+                    found: Type::List(Box::new(Type::Integer(IntegerType::Int64)))
+                }
+            )
+        ])
+    );
+}
+
+#[test]
+fn unknown_operands_are_checked_after_inference() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("let neg = fn(x) { -x } in neg(1.5)"),
+        Ok(Type::Float(FloatType::Float64))
+    );
+    assert_eq!(
+        check_source("let neg = fn(x) { -x } in neg(1) + neg(2)"),
+        Ok(Type::Integer(IntegerType::Int64))
+    );
+    assert_eq!(
+        check_source("fn(x, y) { x * y < x }(1.5, 2.5)"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        check_source("fn(x) { -x }"),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Variable(0)),
+            parameters: vec![Type::Variable(0)]
+        })
+    );
+
+    assert_eq!(
+        check_source("let neg = fn(x) { -x } in neg(\"a\")"),
+        Err(vec![error(
+            1,
+            19,
+            ErrorKind::UnaryOperation {
+                data_type: Type::String,
+                detail: "only numbers can be negated".to_string()
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("let sub = fn(x, y) { x - y } in sub(true, false)"),
+        Err(vec![error(
+            1,
+            24,
+            ErrorKind::BinaryOperation {
+                operator: binary::Operator::Subtract,
+                lhs_type: Type::Boolean,
+                rhs_type: Type::Boolean
+            }
+        )])
+    );
+}
+
+#[test]
+fn sized_numbers_check() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("-128i8 - 1i8"),
+        Ok(Type::Integer(IntegerType::Int8))
+    );
+    assert_eq!(
+        check_source("-9223372036854775808 + 1"),
+        Ok(Type::Integer(IntegerType::Int64))
+    );
+    assert_eq!(
+        check_source("fn(UInt8 x) { x * 2u8 }"),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Integer(IntegerType::UInt8)),
+            parameters: vec![Type::Integer(IntegerType::UInt8)]
+        })
+    );
+    assert_eq!(
+        check_source("1i32 + 0.5f32"),
+        Ok(Type::Float(FloatType::Float32))
+    );
+    assert_eq!(
+        check_source("fn(x) { x as Float32 }"),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Float(FloatType::Float32)),
+            parameters: vec![Type::Variable(0)]
+        })
+    );
+    assert_eq!(check_source("\"abc\"[1u8]"), Ok(Type::Character));
+
+    assert_eq!(
+        check_source("1u8 + 1"),
+        Err(vec![error(
+            1,
+            5,
+            ErrorKind::BinaryOperation {
+                operator: binary::Operator::Add,
+                lhs_type: Type::Integer(IntegerType::UInt8),
+                rhs_type: Type::Integer(IntegerType::Int64)
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("1 as String"),
+        Err(vec![error(
+            1,
+            3,
+            ErrorKind::UnaryOperation {
+                data_type: Type::Integer(IntegerType::Int64),
+                detail: "numbers cannot be converted to String".to_string()
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("true as Int8"),
+        Err(vec![error(
+            1,
+            6,
+            ErrorKind::UnaryOperation {
+                data_type: Type::Boolean,
+                detail: "only numbers can be converted".to_string()
+            }
+        )])
+    );
+}
+
+#[test]
+fn big_integers_check() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(check_source("2n ** 100n % -3n"), Ok(Type::BigInt));
+    assert_eq!(check_source("9223372036854775808"), Ok(Type::BigInt));
+    assert_eq!(
+        check_source("9223372036854775807"),
+        Ok(Type::Integer(IntegerType::Int64))
+    );
+    assert_eq!(check_source("1n < 2.5"), Ok(Type::Boolean));
+    assert_eq!(check_source("fn(BigInt x) { -x }(1n)"), Ok(Type::BigInt));
+    assert_eq!(
+        check_source("(1 as BigInt) as UInt8"),
+        Ok(Type::Integer(IntegerType::UInt8))
+    );
+
+    assert_eq!(check_source("1n * 2"), Ok(Type::BigInt));
+    assert_eq!(check_source("7u8 / 2n"), Ok(Type::BigInt));
+    assert_eq!(
+        check_source("0xFFFF_FFFF_FFFF_FFFF_FFFF > 5"),
+        Ok(Type::Boolean)
+    );
+
+    assert_eq!(
+        check_source("1n ^ 2"),
+        Err(vec![error(
+            1,
+            4,
+            ErrorKind::BinaryOperation {
+                operator: binary::Operator::Xor,
+                lhs_type: Type::BigInt,
+                rhs_type: Type::Integer(IntegerType::Int64)
+            }
+        )])
+    );
+}
+
+#[test]
+fn rationals_check() {
+    assert_eq!(check_source("0.1r + 0.2r == 0.3r"), Ok(Type::Boolean));
+    assert_eq!(check_source("1r / 3 - 2n"), Ok(Type::Rational));
+    assert_eq!(check_source("-0.5r"), Ok(Type::Rational));
+    assert_eq!(
+        check_source("1r / 3 * 1.5f32"),
+        Ok(Type::Float(FloatType::Float32))
+    );
+    assert_eq!(
+        check_source("fn(Rational x) { x as Float64 }(7r)"),
+        Ok(Type::Float(FloatType::Float64))
+    );
+    assert_eq!(check_source("0.25 as Rational"), Ok(Type::Rational));
+}
+
+#[test]
+fn interpolation_checks() {
+    assert_eq!(
+        check_source("let n = 1 in \"{n} is {n > 0} and {\"{n + 1}\"}\""),
+        Ok(Type::String)
+    );
+    assert_eq!(check_source("fn(x) { \"<{x}>\" }(1.5)"), Ok(Type::String));
+
+    assert_eq!(
+        check_source("\"{1 + true}\" + \"{fn(Int64 x) { x }}\""),
+        Err(vec![
+            Error {
+                line: 1,
+                column: 5,
+                kind: ErrorKind::BinaryOperation {
+                    operator: binary::Operator::Add,
+                    lhs_type: Type::Integer(IntegerType::Int64),
+                    rhs_type: Type::Boolean
+                }
+            },
+            Error {
+                line: 1,
+                column: 18,
+                kind: ErrorKind::Interpolation {
+                    data_type: Type::Function {
+                        return_type: Box::new(Type::Integer(IntegerType::Int64)),
+                        parameters: vec![Type::Integer(IntegerType::Int64)]
+                    }
+                }
+            }
+        ])
+    );
+
+    assert_eq!(
+        check_source("let show = fn(x) { \"{x}\" } in show(fn() { 1 })"),
+        Err(vec![Error {
+            line: 1,
+            column: 22,
+            kind: ErrorKind::Interpolation {
+                data_type: Type::Function {
+                    return_type: Box::new(Type::Integer(IntegerType::Int64)),
+                    parameters: Vec::new()
+                }
+            }
+        }])
+    );
+    assert!(check_source("fn(f) { \"{f}\" }(fn() { 1 })").is_err());
+    assert_eq!(
+        check_source("let show = fn(x) { \"{x}\" } in show(1) + show(true)"),
+        Ok(Type::String)
+    );
+}
+
+#[test]
+fn conditionals_check() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("let x = 1 in if x > 0 then \"positive\" else \"negative\""),
+        Ok(Type::String)
+    );
+    assert_eq!(
+        check_source(
+            "let rec factorial = fn(n) { if n == 0 then 1 else n * factorial(n - 1) }
+            in factorial"
+        ),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Integer(IntegerType::Int64)),
+            parameters: vec![Type::Integer(IntegerType::Int64)]
+        })
+    );
+    assert_eq!(
+        check_source("fn(b, x) { if b then x else 1.5 }"),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Float(FloatType::Float64)),
+            parameters: vec![Type::Boolean, Type::Float(FloatType::Float64)]
+        })
+    );
+
+    assert_eq!(
+        check_source("if 1 then true else false"),
+        Err(vec![error(
+            1,
+            1,
+            ErrorKind::TypeMismatch {
+                expected: Type::Boolean,
+                found: Type::Integer(IntegerType::Int64)
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("let x = true in\n  if x then 'a' else \"a\""),
+        Err(vec![error(
+            2,
+            3,
+            ErrorKind::TypeMismatch {
+                expected: Type::Character,
+                found: Type::String
+            }
+        )])
+    );
+    // A condition of the wrong type does not stop the branches being checked
+    assert_eq!(
+        check_source("if \"\" then 1 else y"),
+        Err(vec![
+            error(
+                1,
+                1,
+                ErrorKind::TypeMismatch {
+                    expected: Type::Boolean,
+                    found: Type::String
+                }
+            ),
+            error(
+                1,
+                19,
+                ErrorKind::UnboundIdentifier {
+                    name: "y".to_string()
+                }
+            )
+        ])
+    );
+}
+
+#[test]
+fn types_are_inferred() {
+    let function = |parameters, return_type| Type::Function {
+        return_type: Box::new(return_type),
+        parameters,
+    };
+
+    assert_eq!(
+        check_source("fn(x) { x }"),
+        Ok(function(vec![Type::Variable(0)], Type::Variable(0)))
+    );
+    assert_eq!(
+        check_source("fn(x, y) { x * 2 < y }"),
+        Ok(function(
+            vec![
+                Type::Integer(IntegerType::Int64),
+                Type::Integer(IntegerType::Int64)
+            ],
+            Type::Boolean
+        ))
+    );
+    assert_eq!(
+        check_source("fn(f, x) { f(f(x)) }"),
+        Ok(function(
+            vec![
+                function(vec![Type::Variable(0)], Type::Variable(0)),
+                Type::Variable(0)
+            ],
+            Type::Variable(0)
+        ))
+    );
+    assert_eq!(
+        check_source("let id = fn(x) { x } in id(1) == 1 && id(true)"),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        check_source(
+            "let
+                compose = fn(f, g) { fn(x) { f(g(x)) } },
+                length = fn(s) { s == \"\" }
+            in compose(fn(b) { !b }, length)(\"text\")"
+        ),
+        Ok(Type::Boolean)
+    );
+    assert_eq!(
+        check_source(
+            "let rec
+                even = fn(n) { n == 0 || odd(n - 1) },
+                odd = fn(n) { n != 0 && even(n - 1) }
+            in even"
+        ),
+        Ok(function(
+            vec![Type::Integer(IntegerType::Int64)],
+            Type::Boolean
+        ))
+    );
+}
+
+#[test]
+fn inference_errors_are_reported() {
+    let error = |line, column, kind| Error { line, column, kind };
+
+    assert_eq!(
+        check_source("let increment = fn(x) { x + 1 } in increment(true)"),
+        Err(vec![error(
+            1,
+            45,
+            ErrorKind::TypeMismatch {
+                expected: Type::Integer(IntegerType::Int64),
+                found: Type::Boolean
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("fn(f) { f(1) && f(true) }"),
+        Err(vec![error(
+            1,
+            18,
+            ErrorKind::TypeMismatch {
+                expected: Type::Integer(IntegerType::Int64),
+                found: Type::Boolean
+            }
+        )])
+    );
+    assert_eq!(
+        check_source("fn(x) { x(x) }"),
+        Err(vec![error(
+            1,
+            10,
+            ErrorKind::TypeMismatch {
+                expected: Type::Variable(0),
+                found: Type::Function {
+                    return_type: Box::new(Type::Variable(1)),
+                    parameters: vec![Type::Variable(0)]
+                }
+            }
+        )])
+    );
+    assert_eq!(
+        error(
+            1,
+            10,
+            ErrorKind::TypeMismatch {
+                expected: Type::Variable(0),
+                found: Type::Function {
+                    return_type: Box::new(Type::Variable(1)),
+                    parameters: vec![Type::Variable(0)]
+                }
+            }
+        )
+        .to_string(),
+        "1:10: Expected 'a, found fn('a) -> 'b"
+    );
+}
+
+#[test]
+fn type_errors_display() {
+    assert_eq!(
+        Error {
+            line: 2,
+            column: 5,
+            kind: ErrorKind::BinaryOperation {
+                operator: binary::Operator::Add,
+                lhs_type: Type::Boolean,
+                rhs_type: Type::Character
+            }
+        }
+        .to_string(),
+        "2:5: `+` is not defined for Boolean and Character"
+    );
+}
@@ -0,0 +1,267 @@
+use crate::interpreter::Type;
+
+/// A type that may be generic over some of its type variables, such as the type of a function
+/// bound with `let`, which is instantiated afresh each time it is used
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheme {
+    pub variables: Vec<usize>,
+    pub data_type: Type,
+}
+
+impl From<Type> for Scheme {
+    fn from(data_type: Type) -> Self {
+        Self {
+            variables: Vec::new(),
+            data_type,
+        }
+    }
+}
+
+/// The types that type variables have been unified with
+#[derive(Debug, Default)]
+pub struct Substitution(Vec<Option<Type>>);
+
+impl Substitution {
+    /// A type variable not yet unified with anything
+    pub fn fresh(&mut self) -> Type {
+        self.0.push(None);
+        Type::Variable(self.0.len() - 1)
+    }
+
+    /// Replaces every type variable that has been unified with the type it was unified with
+    pub fn resolve(&self, data_type: &Type) -> Type {
+        match data_type {
+            Type::Variable(variable) => self.0[*variable]
+                .as_ref()
+                .map_or_else(|| data_type.clone(), |bound| self.resolve(bound)),
+            Type::Function {
+                return_type,
+                parameters,
+            } => Type::Function {
+                return_type: Box::new(self.resolve(return_type)),
+                parameters: parameters
+                    .iter()
+                    .map(|parameter| self.resolve(parameter))
+                    .collect(),
+            },
+            Type::List(element) => Type::List(Box::new(self.resolve(element))),
+            Type::Tuple(elements) => Type::Tuple(
+                elements
+                    .iter()
+                    .map(|element| self.resolve(element))
+                    .collect(),
+            ),
+            _ => data_type.clone(),
+        }
+    }
+
+    /// Makes two types equal by binding type variables in either, returning whether it could
+    pub fn unify(&mut self, left: &Type, right: &Type) -> bool {
+        match (self.resolve(left), self.resolve(right)) {
+            (Type::Variable(left), Type::Variable(right)) if left == right => true,
+            (Type::Variable(variable), data_type) | (data_type, Type::Variable(variable)) => {
+                // A type cannot contain itself, as it would be infinitely large
+                if free_variables(&data_type).contains(&variable) {
+                    return false;
+                }
+                self.0[variable] = Some(data_type);
+                true
+            }
+            (
+                Type::Function {
+                    return_type: left_return,
+                    parameters: left_parameters,
+                },
+                Type::Function {
+                    return_type: right_return,
+                    parameters: right_parameters,
+                },
+            ) => {
+                left_parameters.len() == right_parameters.len()
+                    && left_parameters
+                        .iter()
+                        .zip(&right_parameters)
+                        .all(|(left, right)| self.unify(left, right))
+                    && self.unify(&left_return, &right_return)
+            }
+            (Type::List(left), Type::List(right)) => self.unify(&left, &right),
+            (Type::Tuple(left), Type::Tuple(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(&right)
+                        .all(|(left, right)| self.unify(left, right))
+            }
+            (left, right) => left == right,
+        }
+    }
+
+    /// Makes a type generic over the type variables it does not share with `environment`
+    pub fn generalize<'a>(
+        &self,
+        data_type: &Type,
+        environment: impl IntoIterator<Item = &'a Scheme>,
+    ) -> Scheme {
+        let data_type = self.resolve(data_type);
+        let bound: Vec<_> = environment
+            .into_iter()
+            .flat_map(|scheme| {
+                free_variables(&self.resolve(&scheme.data_type))
+                    .into_iter()
+                    .filter(|variable| !scheme.variables.contains(variable))
+            })
+            .collect();
+
+        Scheme {
+            variables: free_variables(&data_type)
+                .into_iter()
+                .filter(|variable| !bound.contains(variable))
+                .collect(),
+            data_type,
+        }
+    }
+
+    /// A type for one use of a scheme, with fresh type variables for its generic variables. The
+    /// related types are instantiated with the same fresh variables, so that they say the same
+    /// about this use as they do about the scheme.
+    pub fn instantiate(&mut self, scheme: &Scheme, related: &[Type]) -> (Type, Vec<Type>) {
+        let fresh: Vec<_> = scheme.variables.iter().map(|_| self.fresh()).collect();
+        let instantiate = |data_type: &Type| {
+            replace_variables(&self.resolve(data_type), &mut |variable| {
+                scheme
+                    .variables
+                    .iter()
+                    .position(|generic| *generic == variable)
+                    .map(|index| fresh[index].clone())
+            })
+        };
+
+        let data_type = instantiate(&scheme.data_type);
+        let related = related.iter().map(instantiate).collect();
+        (data_type, related)
+    }
+}
+
+/// The type variables in a type, in the order they first appear
+pub fn free_variables(data_type: &Type) -> Vec<usize> {
+    let mut variables = Vec::new();
+    replace_variables(data_type, &mut |variable| {
+        if !variables.contains(&variable) {
+            variables.push(variable);
+        }
+        None
+    });
+    variables
+}
+
+/// Numbers the type variables in some types from zero, in the order they first appear, so that
+/// they are displayed as `'a`, `'b` and so on
+pub fn rename_variables<'a>(types: impl IntoIterator<Item = &'a mut Type>) {
+    let mut variables = Vec::new();
+    for data_type in types {
+        *data_type = replace_variables(data_type, &mut |variable| {
+            let index = variables
+                .iter()
+                .position(|renamed| *renamed == variable)
+                .unwrap_or_else(|| {
+                    variables.push(variable);
+                    variables.len() - 1
+                });
+            Some(Type::Variable(index))
+        });
+    }
+}
+
+/// Replaces each type variable with the type `replace` gives for it, if any
+fn replace_variables(data_type: &Type, replace: &mut impl FnMut(usize) -> Option<Type>) -> Type {
+    match data_type {
+        Type::Variable(variable) => replace(*variable).unwrap_or_else(|| data_type.clone()),
+        Type::Function {
+            return_type,
+            parameters,
+        } => {
+            let parameters = parameters
+                .iter()
+                .map(|parameter| replace_variables(parameter, replace))
+                .collect();
+            Type::Function {
+                return_type: Box::new(replace_variables(return_type, replace)),
+                parameters,
+            }
+        }
+        Type::List(element) => Type::List(Box::new(replace_variables(element, replace))),
+        Type::Tuple(elements) => Type::Tuple(
+            elements
+                .iter()
+                .map(|element| replace_variables(element, replace))
+                .collect(),
+        ),
+        _ => data_type.clone(),
+    }
+}
+
+#[test]
+fn types_unify() {
+    use crate::interpreter::{FloatType, IntegerType};
+
+    let mut substitution = Substitution::default();
+    let (a, b) = (substitution.fresh(), substitution.fresh());
+    let function = |parameter: &Type, return_type: &Type| Type::Function {
+        return_type: Box::new(return_type.clone()),
+        parameters: vec![parameter.clone()],
+    };
+
+    assert!(substitution.unify(
+        &function(&a, &Type::Boolean),
+        &function(&Type::Integer(IntegerType::Int64), &b)
+    ));
+    assert_eq!(substitution.resolve(&a), Type::Integer(IntegerType::Int64));
+    assert_eq!(
+        substitution.resolve(&function(&a, &b)),
+        function(&Type::Integer(IntegerType::Int64), &Type::Boolean)
+    );
+    assert!(!substitution.unify(&a, &Type::Float(FloatType::Float64)));
+
+    let c = substitution.fresh();
+    assert!(!substitution.unify(&c, &function(&c, &c)));
+}
+
+#[test]
+fn schemes_generalize_and_instantiate() {
+    let mut substitution = Substitution::default();
+    let (a, b) = (substitution.fresh(), substitution.fresh());
+    let identity = Type::Function {
+        return_type: Box::new(a.clone()),
+        parameters: vec![a.clone()],
+    };
+    let environment = [Scheme::from(b.clone())];
+
+    let scheme = substitution.generalize(&identity, &environment);
+    assert_eq!(scheme.variables, vec![0]);
+    let (instance, related) = substitution.instantiate(&scheme, &[a.clone(), b.clone()]);
+    assert_ne!(instance, identity);
+    assert_eq!(
+        instance,
+        Type::Function {
+            return_type: Box::new(related[0].clone()),
+            parameters: vec![related[0].clone()],
+        }
+    );
+    assert_eq!(related[1], b);
+
+    let constant = Type::Function {
+        return_type: Box::new(environment[0].data_type.clone()),
+        parameters: vec![a],
+    };
+    assert_eq!(
+        substitution.generalize(&constant, &environment).variables,
+        vec![0]
+    );
+
+    let mut types = [Type::Variable(7), Type::Variable(3), Type::Variable(7)];
+    rename_variables(&mut types);
+    assert_eq!(
+        types,
+        [Type::Variable(0), Type::Variable(1), Type::Variable(0)]
+    );
+}
@@ -1,24 +1,42 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::parser::literal::Expression;
+use crate::parser::identifier::Identifier;
 
+pub use big_int::BigInt;
+pub use rational::Rational;
+pub use types::{FloatType, IntegerType};
+pub use value::{Closure, Value};
+
+mod big_int;
+mod conditional;
 mod expression;
+mod interpolation;
+mod let_in;
 mod literal;
+mod rational;
+mod types;
+mod value;
 
-pub trait Evaluates<'a> {
-    fn evaulate(self, scope: Scope<'a>) -> Value;
-}
+/// The most closure calls that can be evaluated inside one another. Recursion is the only way
+/// to loop, so without a limit a program could recurse until the stack overflows and aborts.
+pub const MAX_CALL_DEPTH: usize = 1000;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Value {
-    type_defintion: Type,
-    data: Vec<u8>,
+/// The stack size a thread needs to evaluate [`MAX_CALL_DEPTH`] nested calls without
+/// overflowing, with plenty to spare for calls with deeply nested bodies
+pub const STACK_SIZE: usize = 64 << 20;
+
+pub trait Evaluates<'a> {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error>;
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
-    Integer,
-    Float,
+    Integer(IntegerType),
+    Float(FloatType),
+    /// An integer of any size, which cannot overflow
+    BigInt,
+    /// An exact fraction of integers of any size
+    Rational,
     String,
     Boolean,
     Character,
@@ -26,12 +44,140 @@ pub enum Type {
         return_type: Box<Self>,
         parameters: Vec<Self>,
     },
+    List(Box<Self>),
+    Tuple(Vec<Self>),
+    /// A type that is not known, which may stand for any type in a generic function
+    Variable(usize),
+    /// The type of a value whose type was not annotated. Only the type checker infers types, so
+    /// the interpreter cannot tell what it is.
+    Unknown,
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Scope<'a> {
-    variables: HashMap<&'a str, Value>,
-    enclosing_scope: Option<Rc<Scope<'a>>>,
+    /// Variables bound in this scope. A recursive binding is declared as `None` and initialized
+    /// once its value has been evaluated, which needs the scope to be shared already.
+    variables: RefCell<HashMap<&'a str, Option<Value<'a>>>>,
+    enclosing_scope: Option<Rc<Self>>,
+}
+
+impl<'a> Scope<'a> {
+    /// A scope binding `variables`, nested inside `enclosing_scope`
+    pub fn new(
+        variables: impl IntoIterator<Item = (&'a str, Value<'a>)>,
+        enclosing_scope: Option<Rc<Self>>,
+    ) -> Self {
+        Self {
+            variables: RefCell::new(
+                variables
+                    .into_iter()
+                    .map(|(name, value)| (name, Some(value)))
+                    .collect(),
+            ),
+            enclosing_scope,
+        }
+    }
+
+    /// A scope declaring `names` without values, to be initialized with [`Self::initialize`]
+    pub fn declare(
+        names: impl IntoIterator<Item = &'a str>,
+        enclosing_scope: Option<Rc<Self>>,
+    ) -> Self {
+        Self {
+            variables: RefCell::new(names.into_iter().map(|name| (name, None)).collect()),
+            enclosing_scope,
+        }
+    }
+
+    /// Binds a value to a name in this scope
+    pub fn initialize(&self, name: &'a str, value: Value<'a>) {
+        self.variables.borrow_mut().insert(name, Some(value));
+    }
+
+    /// Finds the value bound to an identifier, searching from the innermost scope outwards and
+    /// reporting where the identifier was used if it is unbound or not yet initialized
+    pub fn lookup(&self, identifier: &Identifier<'a>) -> Result<Value<'a>, Error> {
+        let name = identifier.0.fragment();
+        let mut scope = Some(self);
+
+        while let Some(current) = scope {
+            match current.variables.borrow().get(name) {
+                // A closure bound in a `let rec` only refers weakly to the scope it is bound in
+                Some(Some(Value::Function(closure))) => {
+                    return Ok(Value::Function(Closure::unbind(closure)))
+                }
+                Some(Some(value)) => return Ok(value.clone()),
+                Some(None) => {
+                    return Err(Error::Uninitialized {
+                        name: (*name).to_string(),
+                        line: identifier.0.location_line(),
+                        column: identifier.0.get_utf8_column(),
+                    })
+                }
+                None => scope = current.enclosing_scope.as_deref(),
+            }
+        }
+
+        Err(Error::UnboundIdentifier {
+            name: (*name).to_string(),
+            line: identifier.0.location_line(),
+            column: identifier.0.get_utf8_column(),
+            suggestion: self.closest_name(name).map(ToString::to_string),
+        })
+    }
+
+    /// The bound name most similar to `name`, if one is similar enough to suggest a typo
+    fn closest_name(&self, name: &str) -> Option<&'a str> {
+        let max_distance = (name.chars().count() / 3).max(1);
+        let mut closest = None;
+        let mut scope = Some(self);
+
+        while let Some(current) = scope {
+            for candidate in current.variables.borrow().keys() {
+                let distance = edit_distance(name, candidate);
+                if distance <= max_distance
+                    && closest.is_none_or(|closest| (distance, *candidate) < closest)
+                {
+                    closest = Some((distance, *candidate));
+                }
+            }
+            scope = current.enclosing_scope.as_deref();
+        }
+
+        closest.map(|(_, candidate)| candidate)
+    }
+}
+
+/// The number of single character insertions, deletions, substitutions or swaps of adjacent
+/// characters needed to turn one string into another
+fn edit_distance(from: &str, to: &str) -> usize {
+    let from: Vec<char> = from.chars().collect();
+    let to: Vec<char> = to.chars().collect();
+
+    // distances[i][j] is the distance between the first i characters of `from` and the first j
+    // characters of `to`
+    let mut distances = vec![vec![0; to.len() + 1]; from.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=from.len() {
+        for j in 1..=to.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(from[i - 1] != to[j - 1]);
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && from[i - 1] == to[j - 2] && from[i - 2] == to[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[from.len()][to.len()]
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,6 +191,43 @@ pub enum Error {
         data_type: Type,
         detail: String,
     },
+    Overflow {
+        data_type: Type,
+        detail: String,
+    },
+    UnboundIdentifier {
+        name: String,
+        line: u32,
+        column: usize,
+        suggestion: Option<String>,
+    },
+    Uninitialized {
+        name: String,
+        line: u32,
+        column: usize,
+    },
+    ArgumentCount {
+        expected: usize,
+        found: usize,
+    },
+    UnknownType {
+        name: String,
+        line: u32,
+        column: usize,
+    },
+    DivisionByZero,
+    /// More than [`MAX_CALL_DEPTH`] calls were evaluated inside one another
+    RecursionLimit,
+    TypeMismatch {
+        expected: Type,
+        found: Type,
+    },
+    /// A value with no text to convert to was embedded in an interpolated string
+    Interpolation {
+        data_type: Type,
+        line: u32,
+        column: usize,
+    },
 }
 
 impl std::error::Error for Error {}
@@ -58,11 +241,210 @@ impl std::fmt::Display for Error {
             } => {
                 write!(
                     f,
-                    "Cannot perform binary operation on {:?} and {:?}: {}",
-                    rhs_type, lhs_type, detail
+                    "Cannot perform binary operation on {lhs_type} and {rhs_type}: {detail}"
                 )
             }
-            _ => unimplemented!(),
+            Self::UnaryOperation { data_type, detail } => {
+                write!(f, "Cannot perform unary operation on {data_type}: {detail}")
+            }
+            Self::Overflow { data_type, detail } => {
+                write!(f, "Value does not fit in {data_type}: {detail}")
+            }
+            Self::UnboundIdentifier {
+                name,
+                line,
+                column,
+                suggestion,
+            } => {
+                write!(f, "Unbound identifier `{name}` at {line}:{column}")?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, ", did you mean `{suggestion}`?")?;
+                }
+                Ok(())
+            }
+            Self::Uninitialized { name, line, column } => {
+                write!(
+                    f,
+                    "`{name}` at {line}:{column} is used before it is initialized"
+                )
+            }
+            Self::ArgumentCount { expected, found } => {
+                write!(f, "Expected {expected} arguments, found {found}")
+            }
+            Self::UnknownType { name, line, column } => {
+                write!(f, "Unknown type `{name}` at {line}:{column}")
+            }
+            Self::DivisionByZero => write!(f, "Division by zero"),
+            Self::RecursionLimit => write!(
+                f,
+                "Recursion is too deep: more than {MAX_CALL_DEPTH} calls are in progress"
+            ),
+            Self::TypeMismatch { expected, found } => {
+                write!(f, "Expected {expected}, found {found}")
+            }
+            Self::Interpolation {
+                data_type,
+                line,
+                column,
+            } => {
+                write!(
+                    f,
+                    "Cannot interpolate {data_type} at {line}:{column} into a string"
+                )
+            }
+        }
+    }
+}
+
+#[test]
+fn errors_display() {
+    assert_eq!(
+        Error::BinaryOperation {
+            rhs_type: Type::Character,
+            lhs_type: Type::Boolean,
+            detail: "cannot add".to_string(),
+        }
+        .to_string(),
+        "Cannot perform binary operation on Boolean and Character: cannot add"
+    );
+    assert_eq!(
+        Error::UnaryOperation {
+            data_type: Type::String,
+            detail: "cannot negate".to_string(),
+        }
+        .to_string(),
+        "Cannot perform unary operation on String: cannot negate"
+    );
+    assert_eq!(
+        Error::UnboundIdentifier {
+            name: "x".to_string(),
+            line: 2,
+            column: 5,
+            suggestion: None,
+        }
+        .to_string(),
+        "Unbound identifier `x` at 2:5"
+    );
+    assert_eq!(
+        Error::UnboundIdentifier {
+            name: "lenght".to_string(),
+            line: 1,
+            column: 1,
+            suggestion: Some("length".to_string()),
+        }
+        .to_string(),
+        "Unbound identifier `lenght` at 1:1, did you mean `length`?"
+    );
+    assert_eq!(
+        Error::Uninitialized {
+            name: "x".to_string(),
+            line: 1,
+            column: 13,
+        }
+        .to_string(),
+        "`x` at 1:13 is used before it is initialized"
+    );
+    assert_eq!(
+        Error::ArgumentCount {
+            expected: 2,
+            found: 1,
+        }
+        .to_string(),
+        "Expected 2 arguments, found 1"
+    );
+    assert_eq!(
+        Error::UnknownType {
+            name: "Int".to_string(),
+            line: 1,
+            column: 4,
+        }
+        .to_string(),
+        "Unknown type `Int` at 1:4"
+    );
+    assert_eq!(Error::DivisionByZero.to_string(), "Division by zero");
+    assert_eq!(
+        Error::RecursionLimit.to_string(),
+        "Recursion is too deep: more than 1000 calls are in progress"
+    );
+    assert_eq!(
+        Error::TypeMismatch {
+            expected: Type::Integer(IntegerType::Int64),
+            found: Type::Float(FloatType::Float64),
+        }
+        .to_string(),
+        "Expected Int64, found Float64"
+    );
+    assert_eq!(
+        Error::Interpolation {
+            data_type: Type::Function {
+                return_type: Box::new(Type::Boolean),
+                parameters: Vec::new(),
+            },
+            line: 3,
+            column: 7,
         }
+        .to_string(),
+        "Cannot interpolate fn() -> Boolean at 3:7 into a string"
+    );
+}
+
+#[test]
+fn scopes_resolve_identifiers() {
+    use crate::parser::prelude::Span;
+
+    let global = Rc::new(Scope::new(
+        [("count", Value::from(1)), ("x", Value::from(2))],
+        None,
+    ));
+    let local = Scope::new(
+        [("x", Value::Boolean(true)), ("counter", Value::from(3))],
+        Some(global),
+    );
+    let identifier = |name| Identifier(Span::new(name));
+
+    // Inner bindings shadow outer ones
+    assert_eq!(local.lookup(&identifier("x")), Ok(Value::Boolean(true)));
+    assert_eq!(local.lookup(&identifier("count")), Ok(Value::from(1)));
+    assert_eq!(local.lookup(&identifier("counter")), Ok(Value::from(3)));
+    assert_eq!(
+        local.lookup(&identifier("cuont")),
+        Err(Error::UnboundIdentifier {
+            name: "cuont".to_string(),
+            line: 1,
+            column: 1,
+            suggestion: Some("count".to_string()),
+        })
+    );
+    assert!(matches!(
+        local.lookup(&identifier("unrelated")),
+        Err(Error::UnboundIdentifier {
+            suggestion: None,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn edit_distances() {
+    assert_eq!(edit_distance("", ""), 0);
+    assert_eq!(edit_distance("abc", "abc"), 0);
+    assert_eq!(edit_distance("abc", ""), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("héllo", "hello"), 1);
+    assert_eq!(edit_distance("cuont", "count"), 1);
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::rc::Rc;
+
+    use super::{Error, Evaluates, Value};
+    use crate::parser::prelude::*;
+
+    /// Parse and evaluate a program in an empty scope
+    pub fn evaulate(source: &str) -> Result<Value<'_>, Error> {
+        let (input, expression) = Expression::parse(source.into()).unwrap();
+        assert!(input.is_empty(), "unparsed input: {input}");
+        expression.evaulate(&Rc::default())
     }
 }
@@ -0,0 +1,530 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// An integer of any size.
+///
+/// The magnitude is stored as base 2^32 digits, least significant first, with no trailing
+/// zeros, so that each integer has exactly one representation and zero has no digits.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn new(negative: bool, mut magnitude: Vec<u32>) -> Self {
+        while magnitude.last() == Some(&0) {
+            magnitude.pop();
+        }
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    /// The integer with the given digits, most significant first, in any base up to 2^32
+    pub fn from_digits(digits: &[u8], base: u32, negative: bool) -> Self {
+        let mut magnitude = Vec::new();
+        for &digit in digits {
+            let mut carry = u64::from(digit);
+            for limb in &mut magnitude {
+                let value = u64::from(*limb) * u64::from(base) + carry;
+                *limb = low(value);
+                carry = value >> 32;
+            }
+            if carry > 0 {
+                magnitude.push(low(carry));
+            }
+        }
+        Self::new(negative, magnitude)
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The number of bits in the magnitude, not counting leading zeros
+    pub fn bits(&self) -> u64 {
+        self.magnitude.last().map_or(0, |leading| {
+            32 * self.magnitude.len() as u64 - u64::from(leading.leading_zeros())
+        })
+    }
+
+    /// Division truncating towards zero, or `None` when dividing by zero
+    pub fn checked_div(&self, divisor: &Self) -> Option<Self> {
+        let (quotient, _) = divide(&self.magnitude, &divisor.magnitude)?;
+        Some(Self::new(self.negative != divisor.negative, quotient))
+    }
+
+    /// The remainder of [`Self::checked_div`], which takes the sign of the dividend
+    pub fn checked_rem(&self, divisor: &Self) -> Option<Self> {
+        let (_, remainder) = divide(&self.magnitude, &divisor.magnitude)?;
+        Some(Self::new(self.negative, remainder))
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Self::from(1);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// The integer as an `i128`, if it is in range
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 4 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u128, |value, &limb| value << 32 | u128::from(limb));
+
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    /// The nearest `f64` to the integer, or an infinity if it is too large
+    pub fn to_f64(&self) -> f64 {
+        // Parsing the decimal representation rounds correctly, which is difficult to do by hand
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// The integer equal to a float with no fractional part, or `None` for infinities and NaN
+    pub fn from_integral_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let (negative, mantissa, exponent) = decode_f64(value);
+        let mantissa = Self::from(i128::from(mantissa));
+
+        let scale = Self::from(2).pow(exponent.unsigned_abs());
+        let magnitude = if exponent >= 0 {
+            &mantissa * &scale
+        } else {
+            mantissa.checked_div(&scale).unwrap_or_default()
+        };
+        Some(if negative { -magnitude } else { magnitude })
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = Vec::new();
+        while magnitude > 0 {
+            limbs.push(low(magnitude));
+            magnitude >>= 32;
+        }
+        Self::new(value < 0, limbs)
+    }
+}
+
+/// Splits a finite float into its sign, integer mantissa and binary exponent, so that its
+/// magnitude is exactly `mantissa * 2 ** exponent`
+pub fn decode_f64(value: f64) -> (bool, u64, i32) {
+    let bits = value.to_bits();
+    let biased_exponent = i32::try_from((bits >> 52) & 0x7ff).unwrap_or_default();
+    let fraction = bits & ((1 << 52) - 1);
+
+    // Subnormal numbers have no implicit leading one
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased_exponent - 1075)
+    };
+    (bits >> 63 == 1, mantissa, exponent)
+}
+
+/// The least significant 32 bits of a number
+#[allow(clippy::cast_possible_truncation)]
+fn low(value: impl Into<u128>) -> u32 {
+    value.into() as u32
+}
+
+fn compare_magnitudes(left: &[u32], right: &[u32]) -> Ordering {
+    left.len()
+        .cmp(&right.len())
+        .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let (longer, shorter) = if left.len() >= right.len() {
+        (left, right)
+    } else {
+        (right, left)
+    };
+
+    let mut carry = 0;
+    let mut sum: Vec<_> = longer
+        .iter()
+        .enumerate()
+        .map(|(index, &limb)| {
+            let value =
+                u64::from(limb) + u64::from(shorter.get(index).copied().unwrap_or(0)) + carry;
+            carry = value >> 32;
+            low(value)
+        })
+        .collect();
+    if carry > 0 {
+        sum.push(low(carry));
+    }
+    sum
+}
+
+/// Subtracts a magnitude from one at least as large
+fn subtract_magnitudes(larger: &[u32], smaller: &[u32]) -> Vec<u32> {
+    let mut borrow = 0;
+    larger
+        .iter()
+        .enumerate()
+        .map(|(index, &limb)| {
+            let subtrahend = u64::from(smaller.get(index).copied().unwrap_or(0)) + borrow;
+            let (value, borrowed) = u64::from(limb).overflowing_sub(subtrahend);
+            borrow = u64::from(borrowed);
+            low(value.wrapping_add(u64::from(borrowed) << 32))
+        })
+        .collect()
+}
+
+fn multiply_magnitudes(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut product = vec![0; left.len() + right.len()];
+    for (i, &left) in left.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &right) in right.iter().enumerate() {
+            let value = u64::from(left) * u64::from(right) + u64::from(product[i + j]) + carry;
+            product[i + j] = low(value);
+            carry = value >> 32;
+        }
+        product[i + right.len()] = low(carry);
+    }
+    product
+}
+
+/// Divides a magnitude by a single digit in place, returning the remainder
+fn divide_by_digit(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0;
+    for limb in magnitude.iter_mut().rev() {
+        let value = u64::from(remainder) << 32 | u64::from(*limb);
+        *limb = low(value / u64::from(divisor));
+        remainder = low(value % u64::from(divisor));
+    }
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+    remainder
+}
+
+/// Long division of magnitudes, returning the quotient and remainder, or `None` when dividing
+/// by zero.
+///
+/// This is Knuth's Algorithm D (The Art of Computer Programming, volume 2, section 4.3.1),
+/// which estimates each digit of the quotient from the leading digits of the remainder and
+/// divisor. Shifting both so that the divisor's leading digit has its top bit set makes the
+/// estimate at most two too large.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+fn divide(dividend: &[u32], divisor: &[u32]) -> Option<(Vec<u32>, Vec<u32>)> {
+    let (&leading, _) = divisor.split_last()?;
+
+    if compare_magnitudes(dividend, divisor) == Ordering::Less {
+        return Some((Vec::new(), dividend.to_vec()));
+    }
+    if divisor.len() == 1 {
+        let mut quotient = dividend.to_vec();
+        let remainder = divide_by_digit(&mut quotient, leading);
+        return Some((quotient, vec![remainder]));
+    }
+
+    let shift = leading.leading_zeros();
+    let shift_left = |magnitude: &[u32]| {
+        let mut carry = 0;
+        let mut shifted: Vec<_> = magnitude
+            .iter()
+            .map(|&limb| {
+                let value = u64::from(limb) << shift | carry;
+                carry = value >> 32;
+                low(value)
+            })
+            .collect();
+        shifted.push(low(carry));
+        shifted
+    };
+    let mut divisor = shift_left(divisor);
+    divisor.pop();
+    let mut remainder = shift_left(dividend);
+
+    let length = divisor.len();
+    let mut quotient = vec![0; remainder.len() - length];
+    for j in (0..quotient.len()).rev() {
+        let top = u64::from(remainder[j + length]) << 32 | u64::from(remainder[j + length - 1]);
+        let mut estimate = top / u64::from(divisor[length - 1]);
+        let mut estimate_remainder = top % u64::from(divisor[length - 1]);
+        while estimate >> 32 != 0
+            || estimate * u64::from(divisor[length - 2])
+                > estimate_remainder << 32 | u64::from(remainder[j + length - 2])
+        {
+            estimate -= 1;
+            estimate_remainder += u64::from(divisor[length - 1]);
+            if estimate_remainder >> 32 != 0 {
+                break;
+            }
+        }
+
+        // Subtract the estimate times the divisor from the remainder
+        let mut borrow = 0;
+        let mut carry = 0;
+        for (i, &limb) in divisor.iter().enumerate() {
+            let product = estimate * u64::from(limb) + carry;
+            carry = product >> 32;
+            let value = i64::from(remainder[i + j]) - borrow - i64::from(low(product));
+            remainder[i + j] = value as u32;
+            borrow = i64::from(value < 0);
+        }
+        let value = i64::from(remainder[j + length]) - borrow - carry as i64;
+        remainder[j + length] = value as u32;
+
+        // The estimate was one too large, so add the divisor back
+        if value < 0 {
+            estimate -= 1;
+            let mut carry = 0;
+            for (i, &limb) in divisor.iter().enumerate() {
+                let sum = u64::from(remainder[i + j]) + u64::from(limb) + carry;
+                remainder[i + j] = low(sum);
+                carry = sum >> 32;
+            }
+            remainder[j + length] = remainder[j + length].wrapping_add(low(carry));
+        }
+        quotient[j] = low(estimate);
+    }
+
+    // Undo the shift of the remainder
+    remainder.truncate(length);
+    let remainder = (0..length)
+        .map(|i| {
+            let high = remainder
+                .get(i + 1)
+                .map_or(0, |&limb| u64::from(limb) << 32);
+            low((high | u64::from(remainder[i])) >> shift)
+        })
+        .collect();
+    Some((quotient, remainder))
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: Self) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(
+                self.negative,
+                add_magnitudes(&self.magnitude, &other.magnitude),
+            );
+        }
+
+        // The result has the sign of the operand with the larger magnitude
+        match compare_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::new(
+                other.negative,
+                subtract_magnitudes(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::new(
+                self.negative,
+                subtract_magnitudes(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: Self) -> BigInt {
+        self + &-other.clone()
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: Self) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            multiply_magnitudes(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(!self.negative, self.magnitude)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitudes(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Divide repeatedly by the largest power of ten that fits in a digit, so that each
+        // remainder is a group of nine decimal digits
+        const GROUP: u32 = 1_000_000_000;
+
+        let mut groups = Vec::new();
+        let mut magnitude = self.magnitude.clone();
+        while !magnitude.is_empty() {
+            groups.push(divide_by_digit(&mut magnitude, GROUP));
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match groups.split_last() {
+            Some((first, rest)) => {
+                write!(f, "{first}")?;
+                for group in rest.iter().rev() {
+                    write!(f, "{group:09}")?;
+                }
+                Ok(())
+            }
+            None => write!(f, "0"),
+        }
+    }
+}
+
+#[test]
+fn big_integers_convert() {
+    for value in [0, 1, -1, i128::from(u32::MAX) + 1, i128::MIN, i128::MAX] {
+        assert_eq!(BigInt::from(value).to_i128(), Some(value));
+        assert_eq!(BigInt::from(value).to_string(), value.to_string());
+    }
+    assert_eq!(
+        BigInt::from_digits(&[0xf; 20], 16, false).to_string(),
+        "1208925819614629174706175"
+    );
+    assert_eq!(
+        BigInt::from_digits(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], 10, true).to_string(),
+        "-10000000000"
+    );
+    assert_eq!(BigInt::from_digits(&[1; 130], 2, false).to_i128(), None);
+    assert_eq!(BigInt::from_digits(&[0, 0], 8, true), BigInt::default());
+
+    #[allow(clippy::float_cmp)]
+    for value in [0.0, -3.0, 2f64.powi(100), -1.5e300, 4.9e-324] {
+        let integer = BigInt::from_integral_f64(value.trunc()).unwrap();
+        assert_eq!(integer.to_f64(), value.trunc());
+    }
+    assert_eq!(BigInt::from_integral_f64(f64::NAN), None);
+}
+
+#[test]
+fn floats_decode() {
+    assert_eq!(decode_f64(1.0), (false, 1 << 52, -52));
+    assert_eq!(decode_f64(-0.375), (true, 3 << 51, -54));
+    assert_eq!(decode_f64(-0.0), (true, 0, -1074));
+    assert_eq!(decode_f64(f64::from_bits(1)), (false, 1, -1074));
+    assert_eq!(decode_f64(f64::MAX), (false, (1 << 53) - 1, 971));
+}
+
+#[test]
+fn big_integer_arithmetic() {
+    let big = |value: &str| {
+        let (negative, digits) = value
+            .strip_prefix('-')
+            .map_or((false, value), |digits| (true, digits));
+        let digits: Vec<_> = digits.bytes().map(|digit| digit - b'0').collect();
+        BigInt::from_digits(&digits, 10, negative)
+    };
+
+    let a = big("123456789012345678901234567890");
+    let b = big("-987654321098765432109876543210");
+
+    assert_eq!((&a + &b).to_string(), "-864197532086419753208641975320");
+    assert_eq!((&a - &b).to_string(), "1111111110111111111011111111100");
+    assert_eq!((&b - &b), BigInt::default());
+    assert_eq!(
+        (&a * &b).to_string(),
+        "-121932631137021795226185032733622923332237463801111263526900"
+    );
+    assert_eq!(b.checked_div(&a).unwrap().to_string(), "-8");
+    assert_eq!(
+        b.checked_rem(&a).unwrap().to_string(),
+        "-9000000000900000000090"
+    );
+    assert_eq!(a.checked_div(&BigInt::default()), None);
+    assert_eq!(
+        BigInt::from(2).pow(100).to_string(),
+        "1267650600228229401496703205376"
+    );
+    assert!(b < a && -a.clone() < a && big("-1") > b);
+    assert_eq!(big("0").bits(), 0);
+    assert_eq!(BigInt::from(-5).bits(), 3);
+    assert_eq!(BigInt::from(2).pow(100).bits(), 101);
+
+    // Division satisfies `dividend = quotient * divisor + remainder`, with the remainder
+    // smaller than the divisor, for operands of many different lengths
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = |digits: usize| {
+        let digits: Vec<_> = (0..digits)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                u8::try_from(state % 10).unwrap()
+            })
+            .collect();
+        BigInt::from_digits(&digits, 10, digits.first() == Some(&0))
+    };
+    for length in 1..60 {
+        let dividend = random(length * 3);
+        let divisor = random(length);
+        if divisor.is_zero() {
+            continue;
+        }
+        let quotient = dividend.checked_div(&divisor).unwrap();
+        let remainder = dividend.checked_rem(&divisor).unwrap();
+        assert_eq!(&(&quotient * &divisor) + &remainder, dividend);
+        assert_eq!(
+            compare_magnitudes(&remainder.magnitude, &divisor.magnitude),
+            Ordering::Less
+        );
+        assert!(remainder.is_zero() || remainder.negative == dividend.negative);
+    }
+}
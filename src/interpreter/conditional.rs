@@ -0,0 +1,68 @@
+use std::rc::Rc;
+
+use super::{Error, Evaluates, Scope, Type, Value};
+use crate::parser::conditional::Conditional;
+
+/// Only the branch chosen by the condition is evaluated, so the other branch can fail, or
+/// recurse without end, without affecting the result.
+impl<'a> Evaluates<'a> for Conditional<'a> {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
+        match self.condition.evaulate(scope)? {
+            Value::Boolean(true) => self.consequent.evaulate(scope),
+            Value::Boolean(false) => self.alternative.evaulate(scope),
+            condition => Err(Error::TypeMismatch {
+                expected: Type::Boolean,
+                found: condition.data_type(),
+            }),
+        }
+    }
+}
+
+#[test]
+fn conditional_evaulates() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate("if 1 < 2 then 'a' else 'b'"),
+        Ok(Value::from('a'))
+    );
+    assert_eq!(
+        test::evaulate("let x = 5 in if x < 0 then -1 else if x == 0 then 0 else 1"),
+        Ok(Value::from(1))
+    );
+    assert_eq!(
+        test::evaulate("1 + if false then 1 else 2 * 3"),
+        Ok(Value::from(7))
+    );
+    assert_eq!(
+        test::evaulate(
+            "let rec factorial = fn(Int64 n) -> Int64 {
+                 if n == 0 then 1 else n * factorial(n - 1)
+             } in factorial(10)"
+        ),
+        Ok(Value::from(3_628_800))
+    );
+}
+
+#[test]
+fn untaken_branches_are_not_evaulated() {
+    use super::{test, IntegerType};
+
+    assert_eq!(
+        test::evaulate("if true then 1 else 1 / 0"),
+        Ok(Value::from(1))
+    );
+    assert_eq!(
+        test::evaulate(
+            "let rec forever = fn() -> Int64 { forever() } in if false then forever() else 2"
+        ),
+        Ok(Value::from(2))
+    );
+    assert_eq!(
+        test::evaulate("if 1 then 2 else 3"),
+        Err(Error::TypeMismatch {
+            expected: Type::Boolean,
+            found: Type::Integer(IntegerType::Int64),
+        })
+    );
+}
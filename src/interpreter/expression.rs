@@ -1,11 +1,96 @@
-use super::{Evaluates, Scope, Value};
-use crate::parser::{expression::Expression, literal::Literal};
+use std::rc::Rc;
+
+use super::{Closure, Error, Evaluates, Scope, Value};
+use crate::parser::expression::Expression;
+
+mod binary;
+mod unary;
 
 impl<'a> Evaluates<'a> for Expression<'a> {
-    fn evaulate(self, scope: Scope) -> Value {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
         match self {
             Self::Literal(literal) => literal.evaulate(scope),
-            _ => todo!(),
+            Self::Binary(binary) => binary.evaulate(scope),
+            Self::Unary(unary) => unary.evaulate(scope),
+            Self::Identifier(identifier) => scope.lookup(identifier),
+            Self::LetIn(let_in) => let_in.evaulate(scope),
+            Self::Conditional(conditional) => conditional.evaulate(scope),
+            Self::Interpolation(interpolation) => interpolation.evaulate(scope),
+            Self::Function(function) => Ok(Value::Function(Rc::new(Closure::new(
+                function.clone(),
+                Rc::clone(scope),
+            )?))),
         }
     }
 }
+
+#[test]
+fn unbound_identifier_errors() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate("  abc"),
+        Err(Error::UnboundIdentifier {
+            name: "abc".to_string(),
+            line: 1,
+            column: 3,
+            suggestion: None,
+        })
+    );
+}
+
+#[test]
+fn closures_capture_scope() {
+    use super::{test, IntegerType, Type};
+
+    assert_eq!(
+        test::evaulate("let x = 1, f = fn() -> Int64 { x }, x = 2 in f() + x"),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        test::evaulate(
+            "let add = fn(Int64 x) -> fn(Int64) -> Int64 { fn(Int64 y) -> Int64 { x + y } },
+                 add_one = add(1),
+                 add_ten = add(10)
+             in add_one(2) * add_ten(2)"
+        ),
+        Ok(Value::from(36))
+    );
+    assert_eq!(
+        test::evaulate("fn(Int64 x) -> Int64 { x }").map(|value| value.data_type()),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Integer(IntegerType::Int64)),
+            parameters: vec![Type::Integer(IntegerType::Int64)],
+        })
+    );
+    assert!(matches!(
+        test::evaulate("fn(Int x) -> Int64 { x }"),
+        Err(Error::UnknownType { .. })
+    ));
+}
+
+#[test]
+fn higher_order_functions_evaulate() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate(
+            "let twice = fn(fn(Int64) -> Int64 f) -> fn(Int64) -> Int64 {
+                     fn(Int64 x) -> Int64 { f(f(x)) }
+                 },
+                 double = fn(Int64 x) -> Int64 { x * 2 }
+             in twice(double)(1) + twice(twice(double))(1)"
+        ),
+        Ok(Value::from(20))
+    );
+    assert_eq!(
+        test::evaulate(
+            "let twice = fn(f) { fn(x) { f(f(x)) } } in twice(fn(s) { s + \"!\" })(\"a\")"
+        ),
+        Ok(Value::from("a!!"))
+    );
+    assert_eq!(
+        test::evaulate("fn(x, Boolean y) { y }").map(|value| value.to_string()),
+        Ok("<fn(_, Boolean) -> _>".to_string())
+    );
+}
@@ -0,0 +1,690 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use super::super::{
+    BigInt, Error, Evaluates, FloatType, IntegerType, Rational, Scope, Type, Value,
+};
+use crate::parser::expression::{
+    binary::{Binary, Operator},
+    Expression,
+};
+
+impl<'a> Evaluates<'a> for Binary<'a> {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
+        let Self {
+            left,
+            operator,
+            right,
+            ..
+        } = self;
+
+        let left = left.evaulate(scope)?;
+
+        if let Operator::And | Operator::Or = operator {
+            return short_circuit(operator, &left, right, scope);
+        }
+
+        let right = right.evaulate(scope)?;
+
+        match operator {
+            Operator::Add
+            | Operator::Subtract
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::Exponent
+            | Operator::Modulo => arithmetic(operator, &left, &right),
+            Operator::Xor => exclusive_or(&left, &right),
+            Operator::And | Operator::Or => unreachable!("{operator:?} short-circuits"),
+            Operator::Equal => equal(&left, &right).map(Value::Boolean),
+            Operator::NotEqual => equal(&left, &right).map(|equal| Value::Boolean(!equal)),
+            Operator::LessThan
+            | Operator::LessThanOrEqual
+            | Operator::GreaterThan
+            | Operator::GreaterThanOrEqual => compare(operator, &left, &right),
+        }
+    }
+}
+
+fn unsupported(operator: &Operator, left: &Value, right: &Value) -> Error {
+    Error::BinaryOperation {
+        lhs_type: left.data_type(),
+        rhs_type: right.data_type(),
+        detail: format!("`{}` is not defined for these types", operator.symbol()),
+    }
+}
+
+/// Numeric operands of a binary operation.
+///
+/// Integers of the same type stay integers, while an integer paired with a float is promoted to
+/// the float's type. Numbers of two different integer or float types are not promoted, as it
+/// would be ambiguous which type the result should have. An integer of any type paired with a
+/// `BigInt` is promoted to a `BigInt`, which can hold any integer, so that arithmetic on a
+/// literal too large for `Int64` works with other integers. Any integer paired with a
+/// `Rational` is promoted to a `Rational`, so that dividing it gives an exact fraction, while a
+/// `Rational` paired with a float is rounded to the float's type.
+enum Numbers {
+    Integer(i128, i128, IntegerType),
+    Float(f64, f64, FloatType),
+    BigInt(BigInt, BigInt),
+    Rational(Rational, Rational),
+}
+
+impl Numbers {
+    #[allow(clippy::cast_precision_loss)]
+    fn promote(left: &Value, right: &Value) -> Option<Self> {
+        Some(match (left, right) {
+            (Value::Integer(left, left_type), Value::Integer(right, right_type))
+                if left_type == right_type =>
+            {
+                Self::Integer(*left, *right, *left_type)
+            }
+            (Value::Integer(left, _), Value::Float(right, data_type)) => {
+                Self::Float(data_type.round(*left as f64), *right, *data_type)
+            }
+            (Value::Float(left, data_type), Value::Integer(right, _)) => {
+                Self::Float(*left, data_type.round(*right as f64), *data_type)
+            }
+            (Value::Float(left, left_type), Value::Float(right, right_type))
+                if left_type == right_type =>
+            {
+                Self::Float(*left, *right, *left_type)
+            }
+            (Value::BigInt(left), Value::BigInt(right)) => {
+                Self::BigInt(left.clone(), right.clone())
+            }
+            (Value::BigInt(left), Value::Integer(right, _)) => {
+                Self::BigInt(left.clone(), BigInt::from(*right))
+            }
+            (Value::Integer(left, _), Value::BigInt(right)) => {
+                Self::BigInt(BigInt::from(*left), right.clone())
+            }
+            (Value::BigInt(left), Value::Float(right, data_type)) => {
+                Self::Float(data_type.round(left.to_f64()), *right, *data_type)
+            }
+            (Value::Float(left, data_type), Value::BigInt(right)) => {
+                Self::Float(*left, data_type.round(right.to_f64()), *data_type)
+            }
+            (Value::Rational(left), Value::Rational(right)) => {
+                Self::Rational(left.clone(), right.clone())
+            }
+            (Value::Rational(left), Value::Integer(right, _)) => {
+                Self::Rational(left.clone(), BigInt::from(*right).into())
+            }
+            (Value::Integer(left, _), Value::Rational(right)) => {
+                Self::Rational(BigInt::from(*left).into(), right.clone())
+            }
+            (Value::Rational(left), Value::BigInt(right)) => {
+                Self::Rational(left.clone(), right.clone().into())
+            }
+            (Value::BigInt(left), Value::Rational(right)) => {
+                Self::Rational(left.clone().into(), right.clone())
+            }
+            (Value::Rational(left), Value::Float(right, data_type)) => {
+                Self::Float(left.to_float(*data_type), *right, *data_type)
+            }
+            (Value::Float(left, data_type), Value::Rational(right)) => {
+                Self::Float(*left, right.to_float(*data_type), *data_type)
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// Arithmetic on numbers, and concatenation of strings and characters with `+`.
+///
+/// Integer arithmetic is checked: a result outside the range of the operands' type is an
+/// error, as is dividing by zero, while `BigInt` arithmetic cannot overflow. Division truncates
+/// towards zero and modulo takes the sign of the dividend. `Rational` arithmetic is exact, but
+/// dividing by zero is an error and exponents must be integers. Float arithmetic follows IEEE
+/// 754, rounding each result to the operands' type, so dividing a float by zero produces an
+/// infinity or NaN.
+fn arithmetic<'a>(
+    operator: &Operator,
+    left: &Value<'a>,
+    right: &Value<'a>,
+) -> Result<Value<'a>, Error> {
+    if let (Operator::Add, Value::String(_), Value::String(_) | Value::Character(_))
+    | (Operator::Add, Value::Character(_), Value::String(_)) = (operator, left, right)
+    {
+        return Ok(Value::from(format!("{left}{right}")));
+    }
+
+    match Numbers::promote(left, right) {
+        Some(Numbers::Integer(lhs, rhs, data_type)) => {
+            let overflow = || Error::Overflow {
+                data_type: Type::Integer(data_type),
+                detail: format!("{lhs} {} {rhs} overflows", operator.symbol()),
+            };
+            match operator {
+                Operator::Add => lhs.checked_add(rhs),
+                Operator::Subtract => lhs.checked_sub(rhs),
+                Operator::Multiply => lhs.checked_mul(rhs),
+                Operator::Divide | Operator::Modulo if rhs == 0 => {
+                    return Err(Error::DivisionByZero)
+                }
+                Operator::Divide => lhs.checked_div(rhs),
+                Operator::Modulo => lhs.checked_rem(rhs),
+                Operator::Exponent => integer_power(lhs, rhs, data_type)?,
+                _ => unreachable!("{operator:?} is not an arithmetic operator"),
+            }
+            .filter(|result| data_type.contains(*result))
+            .map(|result| Value::Integer(result, data_type))
+            .ok_or_else(overflow)
+        }
+        Some(Numbers::Float(lhs, rhs, data_type)) => {
+            let result = match operator {
+                Operator::Add => lhs + rhs,
+                Operator::Subtract => lhs - rhs,
+                Operator::Multiply => lhs * rhs,
+                Operator::Divide => lhs / rhs,
+                Operator::Modulo => lhs % rhs,
+                Operator::Exponent => lhs.powf(rhs),
+                _ => unreachable!("{operator:?} is not an arithmetic operator"),
+            };
+            Ok(Value::Float(data_type.round(result), data_type))
+        }
+        Some(Numbers::BigInt(lhs, rhs)) => Ok(Value::BigInt(match operator {
+            Operator::Add => &lhs + &rhs,
+            Operator::Subtract => &lhs - &rhs,
+            Operator::Multiply => &lhs * &rhs,
+            Operator::Divide => lhs.checked_div(&rhs).ok_or(Error::DivisionByZero)?,
+            Operator::Modulo => lhs.checked_rem(&rhs).ok_or(Error::DivisionByZero)?,
+            Operator::Exponent => {
+                let exponent = rhs
+                    .to_i128()
+                    .and_then(|rhs| u32::try_from(rhs).ok())
+                    .ok_or_else(|| Error::BinaryOperation {
+                        lhs_type: Type::BigInt,
+                        rhs_type: Type::BigInt,
+                        detail: format!("cannot raise an integer to the power {rhs}"),
+                    })?;
+                check_power_size(lhs.bits(), exponent, Type::BigInt)?;
+                lhs.pow(exponent)
+            }
+            _ => unreachable!("{operator:?} is not an arithmetic operator"),
+        })),
+        Some(Numbers::Rational(lhs, rhs)) => Ok(Value::Rational(match operator {
+            Operator::Add => &lhs + &rhs,
+            Operator::Subtract => &lhs - &rhs,
+            Operator::Multiply => &lhs * &rhs,
+            Operator::Divide => lhs.checked_div(&rhs).ok_or(Error::DivisionByZero)?,
+            Operator::Modulo => lhs.checked_rem(&rhs).ok_or(Error::DivisionByZero)?,
+            Operator::Exponent => {
+                let exponent = Some(rhs.trunc())
+                    .filter(|_| rhs.denominator() == &BigInt::from(1))
+                    .and_then(|rhs| rhs.to_i128())
+                    .and_then(|rhs| i32::try_from(rhs).ok())
+                    .ok_or_else(|| Error::BinaryOperation {
+                        lhs_type: Type::Rational,
+                        rhs_type: Type::Rational,
+                        detail: format!("cannot raise a rational to the power {rhs}"),
+                    })?;
+                check_power_size(
+                    lhs.numerator().bits().max(lhs.denominator().bits()),
+                    exponent.unsigned_abs(),
+                    Type::Rational,
+                )?;
+                let power = lhs.pow(exponent.unsigned_abs());
+                if exponent < 0 {
+                    power.reciprocal().ok_or(Error::DivisionByZero)?
+                } else {
+                    power
+                }
+            }
+            _ => unreachable!("{operator:?} is not an arithmetic operator"),
+        })),
+        None => Err(unsupported(operator, left, right)),
+    }
+}
+
+/// Raises an integer to a non-negative power, or returns `None` if the result overflows. Zero, one
+/// and negative one have exact powers however large the exponent is, while any other base
+/// overflows long before the exponent leaves `u32`.
+fn integer_power(lhs: i128, rhs: i128, data_type: IntegerType) -> Result<Option<i128>, Error> {
+    if rhs < 0 {
+        return Err(Error::BinaryOperation {
+            lhs_type: Type::Integer(data_type),
+            rhs_type: Type::Integer(data_type),
+            detail: format!("cannot raise an integer to the negative power {rhs}"),
+        });
+    }
+    Ok(match lhs {
+        0 | 1 => Some(if rhs == 0 { 1 } else { lhs }),
+        -1 => Some(if rhs % 2 == 0 { 1 } else { -1 }),
+        _ => u32::try_from(rhs)
+            .ok()
+            .and_then(|exponent| lhs.checked_pow(exponent)),
+    })
+}
+
+/// The most bits the result of raising a `BigInt`, or either part of a `Rational`, to a power
+/// may need. Powers beyond this take too long to compute and too much memory to hold to be of
+/// any use.
+const MAX_POWER_BITS: u64 = 1 << 18;
+
+/// Reports an overflow if raising a number of `bits` bits to `exponent` needs more than
+/// [`MAX_POWER_BITS`]. Such a number is at least `2 ** (bits - 1)`, so its power needs at least
+/// `(bits - 1) * exponent + 1` bits, which is exact for powers of two. Zero, one and negative
+/// one stay the same size whatever the exponent.
+fn check_power_size(bits: u64, exponent: u32, data_type: Type) -> Result<(), Error> {
+    if bits <= 1
+        || (bits - 1)
+            .saturating_mul(u64::from(exponent))
+            .saturating_add(1)
+            <= MAX_POWER_BITS
+    {
+        Ok(())
+    } else {
+        Err(Error::Overflow {
+            data_type,
+            detail: format!(
+                "a number of {bits} bits raised to the power {exponent} would be larger than \
+                 {MAX_POWER_BITS} bits"
+            ),
+        })
+    }
+}
+
+/// `&&` and `||` only evaluate their right operand when the left operand does not decide the
+/// result, so the left operand can guard against errors, as in `x != 0 && 10 / x > 1`
+fn short_circuit<'a>(
+    operator: &Operator,
+    left: &Value<'a>,
+    right: &Expression<'a>,
+    scope: &Rc<Scope<'a>>,
+) -> Result<Value<'a>, Error> {
+    let boolean = |value: &Value| match value {
+        Value::Boolean(value) => Ok(*value),
+        _ => Err(Error::TypeMismatch {
+            expected: Type::Boolean,
+            found: value.data_type(),
+        }),
+    };
+
+    match (operator, boolean(left)?) {
+        (Operator::And, false) => Ok(Value::Boolean(false)),
+        (Operator::Or, true) => Ok(Value::Boolean(true)),
+        _ => boolean(&right.evaulate(scope)?).map(Value::Boolean),
+    }
+}
+
+/// Exclusive or on booleans, doubling as bitwise exclusive or on integers
+fn exclusive_or<'a>(left: &Value<'a>, right: &Value<'a>) -> Result<Value<'a>, Error> {
+    match (left, right) {
+        (Value::Boolean(lhs), Value::Boolean(rhs)) => Ok(Value::Boolean(lhs ^ rhs)),
+        (Value::Integer(lhs, left_type), Value::Integer(rhs, right_type))
+            if left_type == right_type =>
+        {
+            Ok(Value::Integer(lhs ^ rhs, *left_type))
+        }
+        _ => Err(unsupported(&Operator::Xor, left, right)),
+    }
+}
+
+/// Equality is defined between values of the same type, and between integers and floats
+fn equal<'a>(left: &Value<'a>, right: &Value<'a>) -> Result<bool, Error> {
+    match Numbers::promote(left, right) {
+        Some(Numbers::Integer(lhs, rhs, _)) => Ok(lhs == rhs),
+        #[allow(clippy::float_cmp)]
+        Some(Numbers::Float(lhs, rhs, _)) => Ok(lhs == rhs),
+        Some(Numbers::BigInt(lhs, rhs)) => Ok(lhs == rhs),
+        Some(Numbers::Rational(lhs, rhs)) => Ok(lhs == rhs),
+        None if left.data_type() == right.data_type() => Ok(left == right),
+        None => Err(Error::BinaryOperation {
+            lhs_type: left.data_type(),
+            rhs_type: right.data_type(),
+            detail: "values of different types cannot be compared".to_string(),
+        }),
+    }
+}
+
+/// Ordering is defined on numbers, characters and strings (lexicographically).
+///
+/// Comparisons involving NaN are always false.
+fn compare<'a>(
+    operator: &Operator,
+    left: &Value<'a>,
+    right: &Value<'a>,
+) -> Result<Value<'a>, Error> {
+    let ordering = match (left, right) {
+        (Value::Character(lhs), Value::Character(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        _ => match Numbers::promote(left, right) {
+            Some(Numbers::Integer(lhs, rhs, _)) => Some(lhs.cmp(&rhs)),
+            Some(Numbers::Float(lhs, rhs, _)) => lhs.partial_cmp(&rhs),
+            Some(Numbers::BigInt(lhs, rhs)) => Some(lhs.cmp(&rhs)),
+            Some(Numbers::Rational(lhs, rhs)) => Some(lhs.cmp(&rhs)),
+            None => return Err(unsupported(operator, left, right)),
+        },
+    };
+
+    Ok(Value::Boolean(ordering.is_some_and(
+        |ordering| match operator {
+            Operator::LessThan => ordering == Ordering::Less,
+            Operator::LessThanOrEqual => ordering != Ordering::Greater,
+            Operator::GreaterThan => ordering == Ordering::Greater,
+            Operator::GreaterThanOrEqual => ordering != Ordering::Less,
+            _ => unreachable!("{operator:?} is not a comparison operator"),
+        },
+    )))
+}
+
+#[test]
+fn arithmetic_evaulates() {
+    use super::super::test;
+
+    assert_eq!(test::evaulate("1 + 2 * 3"), Ok(Value::from(7)));
+    assert_eq!(test::evaulate("7 - 10"), Ok(Value::from(-3)));
+    assert_eq!(test::evaulate("7 / 2"), Ok(Value::from(3)));
+    assert_eq!(test::evaulate("7 % 4"), Ok(Value::from(3)));
+    assert_eq!(test::evaulate("2 ** 3 ** 2"), Ok(Value::from(512)));
+    assert_eq!(test::evaulate("1 + 0.5"), Ok(Value::from(1.5)));
+    assert_eq!(test::evaulate("0.5 * 4"), Ok(Value::from(2.0)));
+    assert_eq!(test::evaulate("2 ** 0.5"), Ok(Value::from(2f64.sqrt())));
+    assert_eq!(test::evaulate("1.0 / 0"), Ok(Value::from(f64::INFINITY)));
+    assert_eq!(test::evaulate("\"ab\" + 'c'"), Ok(Value::from("abc")));
+    assert_eq!(test::evaulate("\"ab\" + \"cd\""), Ok(Value::from("abcd")));
+    assert_eq!(test::evaulate("6 ^ 3"), Ok(Value::from(5)));
+
+    assert_eq!(test::evaulate("1 / 0"), Err(Error::DivisionByZero));
+    assert_eq!(test::evaulate("1 % 0"), Err(Error::DivisionByZero));
+    assert!(matches!(
+        test::evaulate("9223372036854775807 + 1"),
+        Err(Error::Overflow { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("2 ** 64"),
+        Err(Error::Overflow { .. })
+    ));
+    assert_eq!(test::evaulate("1 ** 5000000000"), Ok(Value::from(1)));
+    assert_eq!(test::evaulate("(-1) ** 5000000001"), Ok(Value::from(-1)));
+    assert_eq!(test::evaulate("(-1) ** 5000000000"), Ok(Value::from(1)));
+    assert_eq!(test::evaulate("0 ** 5000000000"), Ok(Value::from(0)));
+    assert_eq!(test::evaulate("0 ** 0"), Ok(Value::from(1)));
+    assert!(matches!(
+        test::evaulate("2 ** 5000000000"),
+        Err(Error::Overflow { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("2 ** -1"),
+        Err(Error::BinaryOperation { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("'a' + 'b'"),
+        Err(Error::BinaryOperation { .. })
+    ));
+    assert_eq!(
+        test::evaulate("true + 'a'"),
+        Err(Error::BinaryOperation {
+            lhs_type: Type::Boolean,
+            rhs_type: Type::Character,
+            detail: "`+` is not defined for these types".to_string(),
+        })
+    );
+}
+
+#[test]
+fn sized_arithmetic_evaulates() {
+    use super::super::test;
+
+    assert_eq!(
+        test::evaulate("200u8 + 55u8"),
+        Ok(Value::Integer(255, IntegerType::UInt8))
+    );
+    assert_eq!(
+        test::evaulate("200u8 + 56u8"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::UInt8),
+            detail: "200 + 56 overflows".to_string(),
+        })
+    );
+    assert_eq!(
+        test::evaulate("1u64 - 2u64"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::UInt64),
+            detail: "1 - 2 overflows".to_string(),
+        })
+    );
+    assert_eq!(
+        test::evaulate("-128i8 / -1i8"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::Int8),
+            detail: "-128 / -1 overflows".to_string(),
+        })
+    );
+    assert_eq!(
+        test::evaulate("-128i8 - 1i8 + 1i8"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::Int8),
+            detail: "-128 - 1 overflows".to_string(),
+        })
+    );
+    assert_eq!(
+        test::evaulate("-9223372036854775808 + 1"),
+        Ok(Value::from(i64::MIN + 1))
+    );
+    assert_eq!(
+        test::evaulate("2u32 ** 31u32"),
+        Ok(Value::Integer(1 << 31, IntegerType::UInt32))
+    );
+    assert_eq!(
+        test::evaulate("12i16 ^ 10i16"),
+        Ok(Value::Integer(6, IntegerType::Int16))
+    );
+    assert_eq!(
+        test::evaulate("0.1f32 + 0.2f32"),
+        Ok(Value::Float(f64::from(0.1f32 + 0.2f32), FloatType::Float32))
+    );
+    assert_eq!(
+        test::evaulate("1u8 + 0.5f32"),
+        Ok(Value::Float(1.5, FloatType::Float32))
+    );
+    assert_eq!(test::evaulate("3u16 == 3.0"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        test::evaulate("1u8 + 1"),
+        Err(Error::BinaryOperation {
+            lhs_type: Type::Integer(IntegerType::UInt8),
+            rhs_type: Type::Integer(IntegerType::Int64),
+            detail: "`+` is not defined for these types".to_string(),
+        })
+    );
+    assert!(matches!(
+        test::evaulate("1.0f32 < 2.0"),
+        Err(Error::BinaryOperation { .. })
+    ));
+}
+
+#[test]
+fn big_integer_arithmetic_evaulates() {
+    use super::super::test;
+
+    let big = |value: i128| Ok(Value::from(BigInt::from(value)));
+
+    assert_eq!(test::evaulate("9223372036854775807n + 1n"), big(1 << 63));
+    assert_eq!(test::evaulate("2n ** 100n"), big(1 << 100));
+    assert_eq!(test::evaulate("(2n ** 200n / 3n) % 1000n"), big(125));
+    assert_eq!(test::evaulate("-7n / 2n"), big(-3));
+    assert_eq!(test::evaulate("-7n % 2n"), big(-1));
+    assert_eq!(test::evaulate("3n - 0x10n * 2n"), big(-29));
+    assert_eq!(test::evaulate("1n + 0.5"), Ok(Value::from(1.5)));
+    assert_eq!(
+        test::evaulate("2n ** 64n > 2n ** 63n"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(test::evaulate("-5n < 3n"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("3n == 3.0"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("0b1010n != 10n"), Ok(Value::Boolean(false)));
+
+    assert_eq!(test::evaulate("1n / 0n"), Err(Error::DivisionByZero));
+    assert_eq!(test::evaulate("1n % 0n"), Err(Error::DivisionByZero));
+    assert!(matches!(
+        test::evaulate("2n ** -1n"),
+        Err(Error::BinaryOperation { .. })
+    ));
+    assert_eq!(
+        test::evaulate("(-1n) ** 4000000000n"),
+        Ok(Value::from(BigInt::from(1)))
+    );
+    assert_eq!(
+        test::evaulate("2n ** 4000000000n"),
+        Err(Error::Overflow {
+            data_type: Type::BigInt,
+            detail: "a number of 2 bits raised to the power 4000000000 would be larger than \
+                     262144 bits"
+                .to_string(),
+        })
+    );
+    assert_eq!(
+        test::evaulate("2n ** 131072n").map(|value| value.to_string().len()),
+        Ok(39457)
+    );
+    assert_eq!(test::evaulate("2n ** 262143n % 1000n"), big(208));
+    assert_eq!(
+        test::evaulate("2n ** 262144n"),
+        Err(Error::Overflow {
+            data_type: Type::BigInt,
+            detail: "a number of 2 bits raised to the power 262144 would be larger than 262144 \
+                     bits"
+                .to_string(),
+        })
+    );
+    assert_eq!(test::evaulate("1n + 1"), big(2));
+    assert_eq!(test::evaulate("7n / 2"), big(3));
+    assert_eq!(
+        test::evaulate("0xFFFF_FFFF_FFFF_FFFF_FFFF + 1"),
+        big(1 << 80)
+    );
+    assert_eq!(
+        test::evaulate("0xFFFF_FFFF_FFFF_FFFF_FFFF > 5u8"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(test::evaulate("255u8 == 255n"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        test::evaulate("1n ^ 1"),
+        Err(Error::BinaryOperation {
+            lhs_type: Type::BigInt,
+            rhs_type: Type::Integer(IntegerType::Int64),
+            detail: "`^` is not defined for these types".to_string(),
+        })
+    );
+}
+
+#[test]
+fn rational_arithmetic_evaulates() {
+    use super::super::test;
+
+    let rational = |source| test::evaulate(source).map(|value| value.to_string());
+
+    assert_eq!(
+        test::evaulate("0.1r + 0.2r == 0.3r"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(rational("0.1r + 0.2r"), Ok("3/10".to_string()));
+    assert_eq!(rational("1r / 3"), Ok("1/3".to_string()));
+    assert_eq!(rational("1r / 3 * 3"), Ok("1".to_string()));
+    assert_eq!(rational("2 / 4r - 1n"), Ok("-1/2".to_string()));
+    assert_eq!(rational("-7.5r % 2"), Ok("-3/2".to_string()));
+    assert_eq!(rational("(2r / 3) ** -2"), Ok("9/4".to_string()));
+    assert_eq!(rational("1.5e-3r"), Ok("3/2000".to_string()));
+    assert_eq!(test::evaulate("1r / 4 + 0.5"), Ok(Value::from(0.75)));
+    assert_eq!(test::evaulate("1r / 3 < 0.34r"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("4r / 2 == 2"), Ok(Value::Boolean(true)));
+
+    assert_eq!(test::evaulate("1r / 0"), Err(Error::DivisionByZero));
+    assert_eq!(test::evaulate("1r % 0.0r"), Err(Error::DivisionByZero));
+    assert_eq!(test::evaulate("0r ** -1"), Err(Error::DivisionByZero));
+    assert_eq!(
+        test::evaulate("2r ** 0.5r"),
+        Err(Error::BinaryOperation {
+            lhs_type: Type::Rational,
+            rhs_type: Type::Rational,
+            detail: "cannot raise a rational to the power 1/2".to_string(),
+        })
+    );
+    assert_eq!(rational("1.5e1.0r"), Ok("15".to_string()));
+    assert_eq!(rational("(-1r) ** -2000000000"), Ok("1".to_string()));
+    assert_eq!(
+        test::evaulate("(1r / 2) ** 2000000000"),
+        Err(Error::Overflow {
+            data_type: Type::Rational,
+            detail: "a number of 2 bits raised to the power 2000000000 would be larger than \
+                     262144 bits"
+                .to_string(),
+        })
+    );
+}
+
+#[test]
+fn comparison_evaulates() {
+    use super::super::test;
+
+    assert_eq!(test::evaulate("1 < 2"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("2 <= 2"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("1.5 > 2"), Ok(Value::Boolean(false)));
+    assert_eq!(test::evaulate("2 >= 2.0"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("'a' < 'b'"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        test::evaulate("\"abc\" < \"abd\""),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(test::evaulate("1 == 1.0"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("\"a\" != \"a\""), Ok(Value::Boolean(false)));
+    assert_eq!(
+        test::evaulate("1 + 1 == 2 && 'a' != 'b'"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        test::evaulate("false || true ^ true"),
+        Ok(Value::Boolean(false))
+    );
+    assert!(matches!(
+        test::evaulate("1 == 'a'"),
+        Err(Error::BinaryOperation { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("true < false"),
+        Err(Error::BinaryOperation { .. })
+    ));
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    use super::super::test;
+
+    assert_eq!(test::evaulate("true && false"), Ok(Value::Boolean(false)));
+    assert_eq!(test::evaulate("false || true"), Ok(Value::Boolean(true)));
+
+    // The right operand would fail if it were evaluated
+    assert_eq!(
+        test::evaulate("0 != 0 && 10 / 0 > 1"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(test::evaulate("1 == 1 || 1 / 0"), Ok(Value::Boolean(true)));
+    assert_eq!(
+        test::evaulate("false && undefined"),
+        Ok(Value::Boolean(false))
+    );
+    assert_eq!(
+        test::evaulate("true || 'a' + 'b'"),
+        Ok(Value::Boolean(true))
+    );
+
+    // It is evaluated when the left operand does not decide the result
+    assert_eq!(
+        test::evaulate("1 != 0 && 10 / 0 > 1"),
+        Err(Error::DivisionByZero)
+    );
+    assert_eq!(
+        test::evaulate("false || 1"),
+        Err(Error::TypeMismatch {
+            expected: Type::Boolean,
+            found: Type::Integer(IntegerType::Int64),
+        })
+    );
+    assert_eq!(
+        test::evaulate("1 && false"),
+        Err(Error::TypeMismatch {
+            expected: Type::Boolean,
+            found: Type::Integer(IntegerType::Int64),
+        })
+    );
+}
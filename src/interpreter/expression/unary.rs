@@ -0,0 +1,374 @@
+use std::rc::Rc;
+
+use super::super::{BigInt, Error, Evaluates, IntegerType, Rational, Scope, Type, Value};
+use crate::parser::expression::unary::{Operator, Unary};
+
+impl<'a> Evaluates<'a> for Unary<'a> {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
+        let value = self.expression.evaulate(scope)?;
+
+        match (&self.operator, value) {
+            (Operator::Negate, Value::Integer(value, data_type)) => value
+                .checked_neg()
+                .filter(|value| data_type.contains(*value))
+                .map(|value| Value::Integer(value, data_type))
+                .ok_or_else(|| Error::Overflow {
+                    data_type: Type::Integer(data_type),
+                    detail: format!("cannot negate {value}"),
+                }),
+            (Operator::Negate, Value::Float(value, data_type)) => {
+                Ok(Value::Float(-value, data_type))
+            }
+            (Operator::Negate, Value::BigInt(value)) => Ok(Value::BigInt(-value)),
+            (Operator::Negate, Value::Rational(value)) => Ok(Value::Rational(-value)),
+            (Operator::Not, Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+            (Operator::Access(index), Value::String(string)) => {
+                let index = match index.evaulate(scope)? {
+                    Value::Integer(index, _) => index,
+                    index => {
+                        return Err(Error::TypeMismatch {
+                            expected: Type::Integer(IntegerType::Int64),
+                            found: index.data_type(),
+                        })
+                    }
+                };
+
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|index| string.chars().nth(index))
+                    .map(Value::Character)
+                    .ok_or_else(|| Error::UnaryOperation {
+                        data_type: Type::String,
+                        detail: format!(
+                            "index {index} is out of bounds for a string of length {}",
+                            string.chars().count()
+                        ),
+                    })
+            }
+            (Operator::Call(arguments), Value::Function(closure)) => closure.call(
+                arguments
+                    .iter()
+                    .map(|argument| argument.evaulate(scope))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (
+                Operator::Convert(data_type),
+                value @ (Value::Integer(..)
+                | Value::Float(..)
+                | Value::BigInt(_)
+                | Value::Rational(_)),
+            ) => convert(&value, &data_type.try_into()?),
+            (operator, value) => Err(Error::UnaryOperation {
+                data_type: value.data_type(),
+                detail: match operator {
+                    Operator::Negate => "only numbers can be negated",
+                    Operator::Not => "only booleans can be inverted",
+                    Operator::Access(_) => "only strings can be indexed",
+                    Operator::Call(_) => "only functions can be called",
+                    Operator::Convert(_) => "only numbers can be converted",
+                }
+                .to_string(),
+            }),
+        }
+    }
+}
+
+/// Converts a number to another numeric type, which fails if the number is out of the range of
+/// that type. Floats and rationals are truncated towards zero when converted to integers, and
+/// rounded to the nearest value when converted to a less precise float type. Every finite float
+/// converts exactly to a `Rational`.
+fn convert<'a>(value: &Value<'a>, data_type: &Type) -> Result<Value<'a>, Error> {
+    let out_of_range = || Error::Overflow {
+        data_type: data_type.clone(),
+        detail: format!("{value} is out of range"),
+    };
+
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    match (value, data_type) {
+        (Value::Integer(value, _), Type::Integer(data_type)) => Some(*value)
+            .filter(|value| data_type.contains(*value))
+            .map(|value| Value::Integer(value, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::Integer(value, _), Type::Float(data_type)) => {
+            Ok(Value::Float(data_type.round(*value as f64), *data_type))
+        }
+        // Casting saturates, so values too large for an `i128` are still out of range
+        (Value::Float(value, _), Type::Integer(data_type)) => Some(value.trunc())
+            .filter(|value| !value.is_nan())
+            .map(|value| value as i128)
+            .filter(|value| data_type.contains(*value))
+            .map(|value| Value::Integer(value, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::Float(value, _), Type::Float(data_type)) => Some(data_type.round(*value))
+            .filter(|rounded| rounded.is_finite() || !value.is_finite())
+            .map(|rounded| Value::Float(rounded, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::Integer(value, _), Type::BigInt) => Ok(Value::BigInt(BigInt::from(*value))),
+        (Value::Float(value, _), Type::BigInt) => BigInt::from_integral_f64(value.trunc())
+            .map(Value::BigInt)
+            .ok_or_else(out_of_range),
+        (Value::BigInt(value), Type::Integer(data_type)) => value
+            .to_i128()
+            .filter(|value| data_type.contains(*value))
+            .map(|value| Value::Integer(value, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::BigInt(value), Type::Float(data_type)) => Some(data_type.round(value.to_f64()))
+            .filter(|rounded| rounded.is_finite())
+            .map(|rounded| Value::Float(rounded, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::BigInt(value), Type::BigInt) => Ok(Value::BigInt(value.clone())),
+        (Value::Integer(value, _), Type::Rational) => {
+            Ok(Value::Rational(BigInt::from(*value).into()))
+        }
+        (Value::Float(value, _), Type::Rational) => Rational::from_float(*value)
+            .map(Value::Rational)
+            .ok_or_else(out_of_range),
+        (Value::BigInt(value), Type::Rational) => Ok(Value::Rational(value.clone().into())),
+        (Value::Rational(value), Type::Integer(data_type)) => value
+            .trunc()
+            .to_i128()
+            .filter(|value| data_type.contains(*value))
+            .map(|value| Value::Integer(value, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::Rational(value), Type::Float(data_type)) => Some(value.to_float(*data_type))
+            .filter(|rounded| rounded.is_finite())
+            .map(|rounded| Value::Float(rounded, *data_type))
+            .ok_or_else(out_of_range),
+        (Value::Rational(value), Type::BigInt) => Ok(Value::BigInt(value.trunc())),
+        (Value::Rational(value), Type::Rational) => Ok(Value::Rational(value.clone())),
+        (value, _) => Err(Error::UnaryOperation {
+            data_type: value.data_type(),
+            detail: format!("numbers cannot be converted to {data_type}"),
+        }),
+    }
+}
+
+#[test]
+fn unary_operators_evaulate() {
+    use super::super::test;
+
+    assert_eq!(test::evaulate("-(1 + 2)"), Ok(Value::from(-3)));
+    assert_eq!(test::evaulate("--1.5"), Ok(Value::from(1.5)));
+    assert_eq!(test::evaulate("!true"), Ok(Value::Boolean(false)));
+    assert_eq!(test::evaulate("!(1 > 2)"), Ok(Value::Boolean(true)));
+    assert_eq!(test::evaulate("\"héllo\"[1]"), Ok(Value::Character('é')));
+    assert_eq!(test::evaulate("\"abc\"[1 + 1]"), Ok(Value::Character('c')));
+    // Prefix operators bind more tightly than binary operators
+    assert_eq!(test::evaulate("-2 ** 2"), Ok(Value::from(4)));
+
+    assert_eq!(
+        test::evaulate("-true"),
+        Err(Error::UnaryOperation {
+            data_type: Type::Boolean,
+            detail: "only numbers can be negated".to_string(),
+        })
+    );
+    assert!(matches!(
+        test::evaulate("!1"),
+        Err(Error::UnaryOperation { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("\"abc\"[3]"),
+        Err(Error::UnaryOperation { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("\"abc\"[-1]"),
+        Err(Error::UnaryOperation { .. })
+    ));
+    assert_eq!(
+        test::evaulate("\"abc\"['a']"),
+        Err(Error::TypeMismatch {
+            expected: Type::Integer(IntegerType::Int64),
+            found: Type::Character,
+        })
+    );
+    assert!(matches!(
+        test::evaulate("1[0]"),
+        Err(Error::UnaryOperation { .. })
+    ));
+    assert_eq!(
+        test::evaulate("1()"),
+        Err(Error::UnaryOperation {
+            data_type: Type::Integer(IntegerType::Int64),
+            detail: "only functions can be called".to_string(),
+        })
+    );
+}
+
+#[test]
+fn conversions_evaulate() {
+    use super::super::{test, FloatType};
+
+    assert_eq!(
+        test::evaulate("255 as UInt8"),
+        Ok(Value::Integer(255, IntegerType::UInt8))
+    );
+    assert_eq!(
+        test::evaulate("-1.9 as Int8"),
+        Ok(Value::Integer(-1, IntegerType::Int8))
+    );
+    assert_eq!(
+        test::evaulate("16777217 as Float32"),
+        Ok(Value::Float(16_777_216.0, FloatType::Float32))
+    );
+    assert_eq!(
+        test::evaulate("0.1 as Float32 as Float64"),
+        Ok(Value::from(f64::from(0.1f32)))
+    );
+    assert_eq!(test::evaulate("1 + 2u8 as Int64"), Ok(Value::from(3)));
+    assert_eq!(
+        test::evaulate("let x = 5u8 in -x"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::UInt8),
+            detail: "cannot negate 5".to_string(),
+        })
+    );
+
+    assert_eq!(
+        test::evaulate("300 as UInt8"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::UInt8),
+            detail: "300 is out of range".to_string(),
+        })
+    );
+    assert!(matches!(
+        test::evaulate("(2.0 ** 200) as Float32"),
+        Err(Error::Overflow { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("(0.0 / 0.0) as Int32"),
+        Err(Error::Overflow { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("(2.0 ** 64) as UInt64"),
+        Err(Error::Overflow { .. })
+    ));
+    assert_eq!(
+        test::evaulate("1 as Boolean"),
+        Err(Error::UnaryOperation {
+            data_type: Type::Integer(IntegerType::Int64),
+            detail: "numbers cannot be converted to Boolean".to_string(),
+        })
+    );
+    assert_eq!(
+        test::evaulate("'a' as Int64"),
+        Err(Error::UnaryOperation {
+            data_type: Type::Character,
+            detail: "only numbers can be converted".to_string(),
+        })
+    );
+}
+
+#[test]
+fn big_integers_convert() {
+    use super::super::{test, FloatType};
+
+    assert_eq!(
+        test::evaulate("-(2n ** 100n)"),
+        Ok(Value::from(BigInt::from(-(1 << 100))))
+    );
+    assert_eq!(
+        test::evaulate("255u8 as BigInt * 2n"),
+        Ok(Value::from(BigInt::from(510)))
+    );
+    assert_eq!(
+        test::evaulate("-1.5e2 as BigInt"),
+        Ok(Value::from(BigInt::from(-150)))
+    );
+    assert_eq!(
+        test::evaulate("(2.0 ** 100) as BigInt == 2n ** 100n"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        test::evaulate("(2n ** 63n - 1n) as Int64"),
+        Ok(Value::from(i64::MAX))
+    );
+    assert_eq!(
+        test::evaulate("(2n ** 100n) as Float32"),
+        Ok(Value::Float(2f64.powi(100), FloatType::Float32))
+    );
+
+    assert_eq!(
+        test::evaulate("(2n ** 63n) as Int64"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::Int64),
+            detail: "9223372036854775808 is out of range".to_string(),
+        })
+    );
+    assert!(matches!(
+        test::evaulate("(2n ** 200n) as Float32"),
+        Err(Error::Overflow { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("(1.0 / 0.0) as BigInt"),
+        Err(Error::Overflow { .. })
+    ));
+}
+
+#[test]
+fn rationals_convert() {
+    use super::super::{test, FloatType};
+
+    let rational = |source| test::evaulate(source).map(|value| value.to_string());
+
+    assert_eq!(rational("-(1r / 3)"), Ok("-1/3".to_string()));
+    assert_eq!(
+        rational("0.1 as Rational"),
+        Ok("3602879701896397/36028797018963968".to_string())
+    );
+    assert_eq!(rational("3u8 as Rational / 4"), Ok("3/4".to_string()));
+    assert_eq!(
+        rational("(2n ** 70n) as Rational / 2"),
+        Ok("590295810358705651712".to_string())
+    );
+    assert_eq!(
+        test::evaulate("(-7r / 2) as Int8"),
+        Ok(Value::Integer(-3, IntegerType::Int8))
+    );
+    assert_eq!(
+        test::evaulate("(22r / 7) as BigInt"),
+        Ok(Value::from(BigInt::from(3)))
+    );
+    assert_eq!(test::evaulate("0.1r as Float64"), Ok(Value::from(0.1)));
+    assert_eq!(
+        test::evaulate("(1r / 3) as Float32"),
+        Ok(Value::Float(f64::from(1.0f32 / 3.0), FloatType::Float32))
+    );
+
+    assert_eq!(
+        test::evaulate("1000r as UInt8"),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::UInt8),
+            detail: "1000 is out of range".to_string(),
+        })
+    );
+    assert!(matches!(
+        test::evaulate("(1.0 / 0.0) as Rational"),
+        Err(Error::Overflow { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("(2n ** 200n) as Rational as Float32"),
+        Err(Error::Overflow { .. })
+    ));
+}
+
+#[test]
+fn functions_are_called() {
+    use super::super::test;
+
+    assert_eq!(
+        test::evaulate("let f = fn(Int64 x, Int64 y) -> Int64 { x * 10 + y } in f(1, 2)"),
+        Ok(Value::from(12))
+    );
+    assert_eq!(
+        test::evaulate("fn() -> Boolean { true }()"),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        test::evaulate("let f = fn(Int64 x, Int64 y) -> Int64 { x } in f(1)"),
+        Err(Error::ArgumentCount {
+            expected: 2,
+            found: 1
+        })
+    );
+}
@@ -0,0 +1,63 @@
+use std::rc::Rc;
+
+use super::{Error, Evaluates, Scope, Value};
+use crate::parser::interpolation::{Interpolation, Segment};
+
+/// The embedded expressions are evaluated in order and converted to text as they would be
+/// displayed. Functions have no text to convert to, so embedding one is an error.
+impl<'a> Evaluates<'a> for Interpolation<'a> {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
+        let mut text = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(segment) => text.push_str(segment),
+                Segment::Expression { expression, span } => match expression.evaulate(scope)? {
+                    value @ Value::Function(_) => {
+                        return Err(Error::Interpolation {
+                            data_type: value.data_type(),
+                            line: span.location_line(),
+                            column: span.get_utf8_column(),
+                        })
+                    }
+                    value => text.push_str(&value.to_string()),
+                },
+            }
+        }
+        Ok(Value::from(text))
+    }
+}
+
+#[test]
+fn interpolation_evaulates() {
+    use super::{test, Type};
+
+    assert_eq!(
+        test::evaulate("let name = \"Ada\", age = 36 in \"hello {name}, you are {age + 1}\""),
+        Ok(Value::from("hello Ada, you are 37"))
+    );
+    assert_eq!(
+        test::evaulate("\"{1.5} {'c'} {true} {2n ** 70n} {1r / 3} \\{x\\}\""),
+        Ok(Value::from("1.5 c true 1180591620717411303424 1/3 {x}"))
+    );
+    assert_eq!(
+        test::evaulate("let inner = \"{1 + 1}\" in \"[{inner + \"!\"}]\""),
+        Ok(Value::from("[2!]"))
+    );
+    assert_eq!(
+        test::evaulate("let x = 1 in \"\"\"\n    x is\n      {x}\n    \"\"\""),
+        Ok(Value::from("x is\n  1"))
+    );
+
+    assert_eq!(test::evaulate("\"{1 / 0}\""), Err(Error::DivisionByZero));
+    assert_eq!(
+        test::evaulate("let f = fn() -> Boolean { true } in\n\"f is { f }\""),
+        Err(Error::Interpolation {
+            data_type: Type::Function {
+                return_type: Box::new(Type::Boolean),
+                parameters: Vec::new(),
+            },
+            line: 2,
+            column: 9,
+        })
+    );
+}
@@ -0,0 +1,238 @@
+use std::rc::Rc;
+
+use super::{Closure, Error, Evaluates, Scope, Value};
+use crate::parser::let_in::LetIn;
+
+/// Each assignment is evaluated in order and bound in a new scope enclosing the previous one,
+/// so an assignment can refer to the assignments before it.
+///
+/// A binding shadows any earlier binding of the same name, whether in the same `let` or an
+/// enclosing scope. Shadowing never changes the earlier value, so anything that referred to
+/// it beforehand still sees the original.
+///
+/// In a `let rec`, all of the assignments share one scope in which every name is declared up
+/// front, so functions can refer to themselves and each other. The assignments are still
+/// evaluated in order, and using a name before its assignment has been evaluated is an error.
+/// The scope owns the closures bound in it, so they only refer back to it weakly, and looking
+/// one up gives a closure that keeps the scope alive for as long as it is used. This only
+/// applies to closures defined directly in the `let rec` scope: one that refers to it some
+/// other way, such as a closure defined in a nested `let` or returned from a function that was
+/// passed the recursive function, still forms a reference cycle with it, and neither is freed.
+impl<'a> Evaluates<'a> for LetIn<'a> {
+    fn evaulate(&self, scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
+        let scope = if self.recursive {
+            let scope = Rc::new(Scope::declare(
+                self.assignments
+                    .iter()
+                    .map(|assignment| *assignment.identifier.0.fragment()),
+                Some(Rc::clone(scope)),
+            ));
+
+            for assignment in &self.assignments {
+                let value = match assignment.value.evaulate(&scope)? {
+                    Value::Function(closure) => {
+                        Value::Function(Closure::bind_recursively(&closure, &scope))
+                    }
+                    value => value,
+                };
+                scope.initialize(assignment.identifier.0.fragment(), value);
+            }
+
+            scope
+        } else {
+            self.assignments
+                .iter()
+                .try_fold(Rc::clone(scope), |scope, assignment| {
+                    let value = assignment.value.evaulate(&scope)?;
+                    Ok(Rc::new(Scope::new(
+                        [(*assignment.identifier.0.fragment(), value)],
+                        Some(scope),
+                    )))
+                })?
+        };
+
+        self.expression.evaulate(&scope)
+    }
+}
+
+#[test]
+fn let_in_evaulates() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate("let a = 1, b = 2 in a + b"),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        test::evaulate("let a = 2, b = a * 10, c = b + a in c"),
+        Ok(Value::from(22))
+    );
+    assert_eq!(
+        test::evaulate("let a = 1 in let b = a + 1 in a + b"),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        test::evaulate("let a = 1, b = -2, in a - b"),
+        Ok(Value::from(3))
+    );
+    assert_eq!(
+        test::evaulate("let f = fn(Int64 x) { x * 2 }, a = f(1), b = -f(a) in a + b"),
+        Ok(Value::from(-2))
+    );
+}
+
+#[test]
+fn let_in_shadows() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate("let x = 1, x = x + 1 in x"),
+        Ok(Value::from(2))
+    );
+    assert_eq!(
+        test::evaulate("let x = 1 in (let x = 'a' in x)"),
+        Ok(Value::Character('a'))
+    );
+    assert_eq!(
+        test::evaulate("let x = 1 in (let x = 2 in x) + x"),
+        Ok(Value::from(3))
+    );
+}
+
+#[test]
+fn let_in_bindings_do_not_escape() {
+    use super::test;
+
+    assert!(matches!(
+        test::evaulate("(let a = 1 in a) + a"),
+        Err(Error::UnboundIdentifier { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("let a = b, b = 1 in a"),
+        Err(Error::UnboundIdentifier { .. })
+    ));
+}
+
+#[test]
+fn let_rec_evaulates() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate(
+            "let rec count_down = fn(Int64 n) -> Boolean { n == 0 || count_down(n - 1) }
+             in count_down(100)"
+        ),
+        Ok(Value::Boolean(true))
+    );
+    assert_eq!(
+        test::evaulate(
+            "let rec
+                 even = fn(Int64 n) -> Boolean { n == 0 || odd(n - 1) },
+                 odd = fn(Int64 n) -> Boolean { n != 0 && even(n - 1) },
+             in even(10) && odd(7) && !even(3)"
+        ),
+        Ok(Value::Boolean(true))
+    );
+
+    // Non-function values can use functions defined before them
+    assert_eq!(
+        test::evaulate(
+            "let rec
+                 is_even = fn(Int64 n) -> Boolean { n == 0 || !is_even(n - 1) },
+                 result = is_even(4)
+             in result"
+        ),
+        Ok(Value::Boolean(true))
+    );
+}
+
+#[test]
+fn let_rec_errors_before_initialization() {
+    use super::test;
+
+    assert_eq!(
+        test::evaulate("let rec a = b, b = 1 in a"),
+        Err(Error::Uninitialized {
+            name: "b".to_string(),
+            line: 1,
+            column: 13,
+        })
+    );
+    assert!(matches!(
+        test::evaulate(
+            "let rec
+                 result = f(1),
+                 f = fn(Int64 x) -> Int64 { x },
+             in result"
+        ),
+        Err(Error::Uninitialized { .. })
+    ));
+    assert!(matches!(
+        test::evaulate("let rec a = a + 1 in a"),
+        Err(Error::Uninitialized { .. })
+    ));
+
+    // Names from enclosing scopes are shadowed even before initialization
+    assert!(matches!(
+        test::evaulate("let a = 1 in let rec b = a, a = 2 in b"),
+        Err(Error::Uninitialized { .. })
+    ));
+}
+
+#[test]
+fn recursive_scopes_are_freed() {
+    use crate::parser::prelude::*;
+
+    let evaulate = |source, scope: &Rc<Scope<'static>>| {
+        let (_, expression) = Expression::parse(source).unwrap();
+        expression.evaulate(scope).unwrap()
+    };
+    let global = Rc::new(Scope::default());
+
+    // Only the global scope itself refers to it once the recursive scope has been dropped
+    evaulate(
+        "let rec
+             even = fn(Int64 n) -> Boolean { n == 0 || odd(n - 1) },
+             odd = fn(Int64 n) -> Boolean { n != 0 && even(n - 1) },
+         in even(10)"
+            .into(),
+        &global,
+    );
+    assert_eq!(Rc::strong_count(&global), 1);
+
+    // A closure that escapes the `let rec` keeps its scope alive until it is dropped
+    let Value::Function(even) = evaulate(
+        "let rec
+             even = fn(Int64 n) -> Boolean { n == 0 || odd(n - 1) },
+             odd = fn(Int64 n) -> Boolean { n != 0 && even(n - 1) },
+         in even"
+            .into(),
+        &global,
+    ) else {
+        panic!("expected a function");
+    };
+    let scope = Rc::downgrade(&even.scope());
+    assert_eq!(even.call(vec![Value::from(7)]), Ok(Value::from(false)));
+    assert_eq!(scope.strong_count(), 1);
+    drop(even);
+    assert!(scope.upgrade().is_none());
+    assert_eq!(Rc::strong_count(&global), 1);
+
+    // Closures that only reach the recursive scope through another scope or value still form a
+    // cycle with it, so it is never freed
+    for source in [
+        "let rec f = let y = 1 in fn(Int64 n) -> Int64 { if n == 0 then y else f(n - 1) } in f(3)",
+        "let wrap = fn(g) { fn(n) { g(n) } } in
+         let rec f = wrap(fn(n) { if n == 0 then 0 else f(n - 1) }) in f(3)",
+    ] {
+        let global = Rc::new(Scope::default());
+        evaulate(source.into(), &global);
+        assert!(Rc::strong_count(&global) > 1);
+    }
+
+    // Each lookup gives a new closure, but it is still the same function
+    assert_eq!(
+        super::test::evaulate("let rec f = fn() -> Int64 { f() } in f == f"),
+        Ok(Value::from(true))
+    );
+}
@@ -1,21 +1,20 @@
-use super::{Evaluates, Scope, Type, Value};
+use std::rc::Rc;
+
+use super::{Error, Evaluates, Scope, Value};
 use crate::parser::literal::Literal;
 
 mod float;
+mod integer;
+mod string;
 
 impl<'a> Evaluates<'a> for Literal {
-    fn evaulate(self, _scope: Scope) -> Value {
-        match self {
-            Self::Boolean(value) => Value {
-                type_defintion: Type::Boolean,
-                data: vec![u8::from(value.0)],
-            },
-            Self::Character(value) => Value {
-                type_defintion: Type::Character,
-                data: (value.0 as u32).to_be_bytes().to_vec(),
-            },
-            Self::Float(value) => value.into(),
-            _ => todo!(),
-        }
+    fn evaulate(&self, _scope: &Rc<Scope<'a>>) -> Result<Value<'a>, Error> {
+        Ok(match self.clone() {
+            Self::Boolean(value) => value.0.into(),
+            Self::Character(value) => value.0.into(),
+            Self::Float(value) => value.try_into()?,
+            Self::Integer(value) => value.try_into()?,
+            Self::String(value) => value.into(),
+        })
     }
 }
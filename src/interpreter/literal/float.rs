@@ -1,54 +1,151 @@
-use super::super::{Type, Value};
+use super::super::{BigInt, Error, FloatType, Rational, Type, Value};
 use crate::parser::prelude::*;
 use literal::Float;
-use number::{float::Exponent, Sign};
+use number::{
+    float::{Exponent, Suffix},
+    Base, Sign,
+};
 
-impl From<Float> for Value {
-    fn from(value: Float) -> Self {
-        Self {
-            type_defintion: Type::Float,
-            data: f64::from(value).to_be_bytes().to_vec(),
+impl TryFrom<Float> for Value<'_> {
+    type Error = Error;
+
+    fn try_from(value: Float) -> Result<Self, Self::Error> {
+        match value
+            .suffix
+            .map_or(Type::Float(FloatType::Float64), Type::from)
+        {
+            Type::Float(data_type) => Ok(Self::Float(nearest(&value, data_type), data_type)),
+            _ => exact(&value).map(Self::Rational),
+        }
+    }
+}
+
+impl From<Suffix> for Type {
+    fn from(suffix: Suffix) -> Self {
+        match suffix {
+            Suffix::F32 => Self::Float(FloatType::Float32),
+            Suffix::F64 => Self::Float(FloatType::Float64),
+            Suffix::Rational => Self::Rational,
         }
     }
 }
 
 impl From<Float> for f64 {
     fn from(value: Float) -> Self {
-        let Float {
-            base,
-            whole,
-            fractional,
-            sign,
-            exponent,
-        } = value;
-
-        let base = base as u8;
-
-        let (_, whole_value) = whole
-            .into_iter()
-            .fold((0, 0.0), |(place_value, value), digit| {
-                (
-                    place_value + 1,
-                    Self::from(digit).mul_add(Self::from(base).powi(place_value), value),
-                )
-            });
-
-        let (_, fractional_value) =
-            fractional
-                .into_iter()
-                .fold((-1, 0.0), |(place_value, value), digit| {
-                    (
-                        place_value - 1,
-                        Self::from(digit).mul_add(Self::from(base).powi(place_value), value),
-                    )
-                });
-
-        let exponent = exponent.map_or(1.0, |exponent| 10f64.powf(exponent.into()));
-
-        (whole_value + fractional_value).copysign(match sign {
-            Sign::Positive => 1.0,
-            Sign::Negative => -1.0,
-        }) * exponent
+        nearest(&value, FloatType::Float64)
+    }
+}
+
+/// The value of a float literal, rounded to the nearest value of a float type.
+///
+/// The digits and the integer part of the exponent are combined exactly into a ratio of big
+/// integers, which is rounded only once so that the result is correctly rounded. The exponent
+/// is a power of ten for decimal floats and a power of two otherwise, so floats in other bases
+/// can give every bit of a value exactly. A fractional exponent generally makes the value
+/// irrational, so its fractional part is applied afterwards.
+#[allow(clippy::cast_precision_loss)]
+fn nearest(float: &Float, data_type: FloatType) -> f64 {
+    let (exponent, exponent_fraction) = float.exponent.as_ref().map_or((0, 0.0), split_exponent);
+    let radix = if float.base == Base::Decimal { 10 } else { 2 };
+    let base = float.base.clone() as u32;
+    let mantissa = mantissa(float);
+
+    // Estimate the binary exponent of the value, to avoid computing huge powers for values
+    // that are far outside the range of the type anyway
+    let (precision, min_exponent, max_exponent) = data_type.parameters();
+    let estimate = (exponent as f64).mul_add(
+        f64::from(radix).log2(),
+        (float.fractional.len() as f64).mul_add(-f64::from(base).log2(), mantissa.bits() as f64),
+    );
+
+    let magnitude = if mantissa.is_zero() || estimate < f64::from(min_exponent - precision - 4) {
+        0.0
+    } else if estimate > f64::from(max_exponent + 4) {
+        f64::INFINITY
+    } else {
+        let (numerator, denominator) = ratio(float, mantissa, exponent);
+        data_type.round_ratio(&numerator, &denominator)
+    };
+
+    let magnitude = if exponent_fraction == 0.0 {
+        magnitude
+    } else {
+        data_type.round(magnitude * f64::from(radix).powf(exponent_fraction))
+    };
+    match float.sign {
+        Sign::Positive => magnitude,
+        Sign::Negative => -magnitude,
+    }
+}
+
+/// The exact value of a float literal, which needs an exponent small enough that its power can
+/// be computed. Exact literals with a fractional exponent are rejected by the parser.
+fn exact(float: &Float) -> Result<Rational, Error> {
+    let (exponent, _) = float.exponent.as_ref().map_or((0, 0.0), split_exponent);
+    if exponent.unsigned_abs() > u64::from(u16::MAX) {
+        return Err(Error::Overflow {
+            data_type: Type::Rational,
+            detail: "the exponent of a rational literal is too large".to_string(),
+        });
+    }
+
+    let mantissa = mantissa(float);
+    let mantissa = match float.sign {
+        Sign::Positive => mantissa,
+        Sign::Negative => -mantissa,
+    };
+    let (numerator, denominator) = ratio(float, mantissa, exponent);
+    Ok(Rational::new(&numerator, &denominator).unwrap_or_default())
+}
+
+/// All of the digits of a float literal as one integer, ignoring the point
+fn mantissa(float: &Float) -> BigInt {
+    let digits: Vec<_> = float
+        .whole
+        .iter()
+        .chain(&float.fractional)
+        .copied()
+        .collect();
+    BigInt::from_digits(&digits, float.base.clone() as u32, false)
+}
+
+/// The numerator and denominator of a float literal's value, given its mantissa and the integer
+/// part of its exponent, which is a power of ten for decimal floats and a power of two otherwise
+fn ratio(float: &Float, mantissa: BigInt, exponent: i64) -> (BigInt, BigInt) {
+    let radix = if float.base == Base::Decimal { 10 } else { 2 };
+    let power = |base: u32, exponent: u64| {
+        BigInt::from(i128::from(base)).pow(u32::try_from(exponent).unwrap_or(u32::MAX))
+    };
+
+    let denominator = power(float.base.clone() as u32, float.fractional.len() as u64);
+    let scale = power(radix, exponent.unsigned_abs());
+    if exponent < 0 {
+        (mantissa, &denominator * &scale)
+    } else {
+        (&mantissa * &scale, denominator)
+    }
+}
+
+/// The integer and fractional parts of an exponent, saturating if the integer part is too large
+/// for an `i64`
+fn split_exponent(exponent: &Exponent) -> (i64, f64) {
+    let Exponent {
+        whole,
+        fractional,
+        sign,
+    } = exponent;
+
+    let whole = whole.iter().fold(0i64, |value, &digit| {
+        value.saturating_mul(10).saturating_add(digit.into())
+    });
+    let fractional = fractional
+        .iter()
+        .rev()
+        .fold(0.0, |value, &digit| (value + f64::from(digit)) / 10.0);
+
+    match sign {
+        Sign::Positive => (whole, fractional),
+        Sign::Negative => (-whole, -fractional),
     }
 }
 
@@ -62,6 +159,7 @@ fn float_evaulates() {
             fractional: Vec::new(),
             sign: Sign::Positive,
             exponent: None,
+            suffix: None,
         }),
         1.0f64
     );
@@ -73,6 +171,7 @@ fn float_evaulates() {
             fractional: vec![0x8],
             sign: Sign::Negative,
             exponent: None,
+            suffix: None,
         }),
         -255.5f64
     );
@@ -88,41 +187,192 @@ fn float_evaulates() {
                 fractional: Vec::new(),
                 sign: Sign::Positive
             }),
+            suffix: None,
         }),
-        1000f64
+        8f64
+    );
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn binary_exponents_evaulate() {
+    let evaulate = |source| Value::try_from(Float::parse(source).unwrap().1).unwrap();
+
+    assert_eq!(evaulate("0x1.8p3".into()), Value::from(12.0));
+    assert_eq!(evaulate("0x1.0p-2".into()), Value::from(0.25));
+    assert_eq!(evaulate("0o7.4p1".into()), Value::from(15.0));
+    assert_eq!(evaulate("-0b1.01p10".into()), Value::from(-1280.0));
+
+    // Hexadecimal floats give bit-exact constants
+    assert_eq!(
+        evaulate("0x1.fffffffffffffp1023".into()),
+        Value::from(f64::MAX)
+    );
+    assert_eq!(
+        evaulate("0x0.0000000000001p-1022".into()),
+        Value::from(f64::from_bits(1))
+    );
+    assert_eq!(
+        evaulate("0x1.921fb54442d18p1".into()),
+        Value::from(std::f64::consts::PI)
+    );
+    assert_eq!(
+        evaulate("0x1.fffffep127f32".into()),
+        Value::Float(f32::MAX.into(), FloatType::Float32)
+    );
+
+    // Values past the end of the range round to infinity, or to zero when they are too small
+    assert_eq!(evaulate("0x1.0p1024".into()), Value::from(f64::INFINITY));
+    assert_eq!(evaulate("0x1.0p-1076".into()), Value::from(0.0));
+    assert_eq!(
+        evaulate("0x1.0p-1075".into()),
+        Value::from(0.0),
+        "ties round to even"
+    );
+    assert_eq!(
+        evaulate("0x1.8p-1075".into()),
+        Value::from(f64::from_bits(1))
     );
 }
 
-impl From<Exponent> for f64 {
-    fn from(value: Exponent) -> Self {
-        let Exponent {
-            whole,
-            fractional,
-            sign,
-        } = value;
-
-        let (_, whole_value) = whole
-            .into_iter()
-            .fold((0, 0.0), |(place_value, value), digit| {
-                (
-                    place_value + 1,
-                    Self::from(digit).mul_add(10f64.powi(place_value), value),
-                )
-            });
-
-        let (_, fractional_value) =
-            fractional
-                .into_iter()
-                .fold((-1, 0.0), |(place_value, value), digit| {
-                    (
-                        place_value - 1,
-                        Self::from(digit).mul_add(10f64.powi(place_value), value),
-                    )
-                });
-
-        (whole_value + fractional_value).copysign(match sign {
-            Sign::Positive => 1.0,
-            Sign::Negative => -1.0,
-        })
+/// A xorshift generator, so that the random literals are the same on every run
+#[cfg(test)]
+const fn random(state: &mut u64, range: u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state % range
+}
+
+#[cfg(test)]
+fn random_digits(state: &mut u64, base: u64, lengths: std::ops::Range<u64>) -> Vec<u8> {
+    let length = lengths.start + random(state, lengths.end - lengths.start);
+    (0..length)
+        .map(|_| u8::try_from(random(state, base)).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+fn digits_text(digits: &[u8]) -> String {
+    digits
+        .iter()
+        .map(|&digit| char::from_digit(digit.into(), 16).unwrap())
+        .collect()
+}
+
+/// Checks that a literal converts to the same floats as the standard library parses its exact
+/// decimal value to, comparing bit patterns so that the sign of zero matters
+#[cfg(test)]
+fn check_rounding(source: &str, decimal: &str) {
+    let convert = |data_type| {
+        let (input, float) = Float::parse(source.into()).unwrap();
+        assert!(input.is_empty(), "unparsed input in {source}: {input}");
+        nearest(&float, data_type).to_bits()
+    };
+
+    assert_eq!(
+        convert(FloatType::Float64),
+        decimal.parse::<f64>().unwrap().to_bits(),
+        "{source} as Float64"
+    );
+    assert_eq!(
+        convert(FloatType::Float32),
+        f64::from(decimal.parse::<f32>().unwrap()).to_bits(),
+        "{source} as Float32"
+    );
+}
+
+#[test]
+fn float_literals_round_correctly() {
+    for source in [
+        "0.0",
+        "-0.0",
+        "0.1",
+        "0.3",
+        "1.0e-300",
+        "1.0e308",
+        "1.7976931348623157e308",
+        "1.7976931348623158e308",
+        "1.7976931348623159e308",
+        "2.2250738585072011e-308",
+        "2.2250738585072014e-308",
+        "4.9406564584124654e-324",
+        "2.4703282292062327e-324",
+        "2.4703282292062328e-324",
+        "1.0e-400",
+        "1.0e400",
+        "9007199254740993.0",
+        "9007199254740995.0",
+        "0.1000000000000000055511151231257827021181583404541015625",
+        "0.1000000000000000055511151231257827021181583404541015624",
+        "3.4028235677973366e38",
+        "3.4028236e38",
+        "1.4012984643e-45",
+        "7.006492321624085e-46",
+        "7.006492321624086e-46",
+        "16777217.0",
+        "123456789012345678901234567890.123456789012345678901234567890e-10",
+    ] {
+        check_rounding(source, source);
+    }
+
+    let state = &mut 0x9e37_79b9_7f4a_7c15;
+    // Decimal literals of all lengths and magnitudes
+    for _ in 0..3000 {
+        let length = if random(state, 10) == 0 { 60 } else { 20 };
+        let whole = random_digits(state, 10, 0..length);
+        let fractional = random_digits(state, 10, 1..length);
+        let exponent = match random(state, 3) {
+            0 => String::new(),
+            1 => format!("e{}", random(state, 40)),
+            _ => format!("e-{}", random(state, 360)),
+        };
+        let source = format!(
+            "{}.{}{exponent}",
+            digits_text(&whole),
+            digits_text(&fractional)
+        );
+        check_rounding(&source, &source);
+    }
+}
+
+#[test]
+fn other_bases_round_correctly() {
+    let state = &mut 0x2545_f491_4f6c_dd1d;
+
+    // Literals in other bases are exact binary fractions, so they have a finite decimal
+    // expansion to compare against: m / 2^n = m * 5^n / 10^n
+    for (prefix, base, bits) in [("0b", 2, 1), ("0o", 8, 3), ("0x", 16, 4)] {
+        for _ in 0..1000 {
+            let whole = random_digits(state, base, 0..200 / bits);
+            let fractional = random_digits(state, base, 1..200 / bits);
+            let exponent = i64::try_from(random(state, 2400)).unwrap() - 1200;
+            // Only some of the literals have an exponent
+            let suffix = if exponent % 2 == 0 {
+                format!("p{exponent}")
+            } else {
+                String::new()
+            };
+            let source = format!(
+                "{prefix}{}.{}{suffix}",
+                digits_text(&whole),
+                digits_text(&fractional)
+            );
+
+            let all: Vec<_> = whole.iter().chain(&fractional).copied().collect();
+            let mantissa = BigInt::from_digits(&all, u32::try_from(base).unwrap(), false);
+            let fractional_bits = i64::try_from(fractional.len() as u64 * bits).unwrap();
+            let power = if exponent % 2 == 0 {
+                exponent - fractional_bits
+            } else {
+                -fractional_bits
+            };
+            let places = u32::try_from(-power.min(0)).unwrap();
+            let scaled = &(&mantissa * &BigInt::from(2).pow(u32::try_from(power.max(0)).unwrap()))
+                * &BigInt::from(5).pow(places);
+            let decimal = format!("{:0>1$}", scaled.to_string(), places as usize + 1);
+            let (whole, fractional) = decimal.split_at(decimal.len() - places as usize);
+            check_rounding(&source, &format!("{whole}.{fractional}"));
+        }
     }
 }
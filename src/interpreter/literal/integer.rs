@@ -0,0 +1,206 @@
+use super::super::{BigInt, Error, IntegerType, Type, Value};
+use crate::parser::prelude::*;
+use literal::Integer;
+use number::{integer::Suffix, Sign};
+
+impl TryFrom<Integer> for Value<'_> {
+    type Error = Error;
+
+    fn try_from(integer: Integer) -> Result<Self, Self::Error> {
+        let value = BigInt::from(&integer);
+
+        match Type::from(&integer) {
+            Type::Integer(data_type) => value
+                .to_i128()
+                .filter(|value| data_type.contains(*value))
+                .map(|value| Self::Integer(value, data_type))
+                .ok_or_else(|| Error::Overflow {
+                    data_type: Type::Integer(data_type),
+                    detail: format!(
+                        "integer literal with {} digits in base {} is out of range",
+                        integer.digits.len(),
+                        integer.base.clone() as u32
+                    ),
+                }),
+            Type::Rational => Ok(Self::Rational(value.into())),
+            _ => Ok(Self::BigInt(value)),
+        }
+    }
+}
+
+/// The exact value of an integer literal, whatever its type
+impl From<&Integer> for BigInt {
+    fn from(integer: &Integer) -> Self {
+        Self::from_digits(
+            &integer.digits,
+            integer.base.clone() as u32,
+            integer.sign == Sign::Negative,
+        )
+    }
+}
+
+/// The type of an integer literal, which is the type named by its suffix if it has one.
+/// Otherwise it is `Int64`, unless the literal is too large for one and so is a `BigInt`.
+impl From<&Integer> for Type {
+    fn from(integer: &Integer) -> Self {
+        match integer.suffix {
+            Some(suffix) => suffix.into(),
+            None if BigInt::from(integer)
+                .to_i128()
+                .is_some_and(|value| IntegerType::Int64.contains(value)) =>
+            {
+                Self::Integer(IntegerType::Int64)
+            }
+            None => Self::BigInt,
+        }
+    }
+}
+
+impl From<Suffix> for Type {
+    fn from(suffix: Suffix) -> Self {
+        match suffix {
+            Suffix::I8 => Self::Integer(IntegerType::Int8),
+            Suffix::I16 => Self::Integer(IntegerType::Int16),
+            Suffix::I32 => Self::Integer(IntegerType::Int32),
+            Suffix::I64 => Self::Integer(IntegerType::Int64),
+            Suffix::U8 => Self::Integer(IntegerType::UInt8),
+            Suffix::U16 => Self::Integer(IntegerType::UInt16),
+            Suffix::U32 => Self::Integer(IntegerType::UInt32),
+            Suffix::U64 => Self::Integer(IntegerType::UInt64),
+            Suffix::Big => Self::BigInt,
+            Suffix::Rational => Self::Rational,
+        }
+    }
+}
+
+#[test]
+fn integer_evaulates() {
+    use number::Base;
+
+    assert_eq!(
+        Value::try_from(Integer {
+            base: Base::Decimal,
+            digits: vec![4, 2],
+            sign: Sign::Positive,
+            suffix: None,
+        }),
+        Ok(Value::from(42))
+    );
+
+    assert_eq!(
+        Value::try_from(Integer {
+            base: Base::Hexadecimal,
+            digits: vec![0xf, 0xf],
+            sign: Sign::Negative,
+            suffix: None,
+        }),
+        Ok(Value::from(-255))
+    );
+
+    assert_eq!(
+        Value::try_from(Integer {
+            base: Base::Octal,
+            digits: vec![1, 7],
+            sign: Sign::Positive,
+            suffix: None,
+        }),
+        Ok(Value::from(0o17))
+    );
+
+    assert_eq!(
+        Value::try_from(Integer {
+            base: Base::Binary,
+            digits: vec![1, 0, 1],
+            sign: Sign::Positive,
+            suffix: None,
+        }),
+        Ok(Value::from(0b101))
+    );
+
+    assert_eq!(
+        Value::try_from(Integer::parse("1_000_000".into()).unwrap().1),
+        Ok(Value::from(1_000_000))
+    );
+
+    assert_eq!(
+        Value::try_from(Integer::parse("-9223372036854775808".into()).unwrap().1),
+        Ok(Value::from(i64::MIN))
+    );
+}
+
+#[test]
+fn big_integers_evaulate() {
+    use super::super::Rational;
+
+    let binary = format!("0b1{}", "0".repeat(100));
+    let evaulate = |source| Value::try_from(Integer::parse(source).unwrap().1);
+
+    // Literals too large for an `Int64` are `BigInt`s, in every base
+    assert_eq!(
+        evaulate("9223372036854775808".into()),
+        Ok(Value::from(BigInt::from(1 << 63)))
+    );
+    assert_eq!(
+        evaulate("-0x8000_0000_0000_0001".into()),
+        Ok(Value::from(BigInt::from(-(1 << 63) - 1)))
+    );
+    assert_eq!(
+        evaulate("0o1_000_000_000_000_000_000_000".into()),
+        Ok(Value::from(BigInt::from(1 << 63)))
+    );
+    assert_eq!(
+        evaulate(binary.as_str().into()),
+        Ok(Value::from(BigInt::from(1 << 100)))
+    );
+    assert_eq!(
+        evaulate("340282366920938463463374607431768211456".into()).map(|value| value.to_string()),
+        Ok("340282366920938463463374607431768211456".to_string())
+    );
+
+    // Smaller literals are opted in with a suffix
+    assert_eq!(evaulate("42n".into()), Ok(Value::from(BigInt::from(42))));
+    assert_eq!(
+        evaulate("-0xffn".into()),
+        Ok(Value::from(BigInt::from(-255)))
+    );
+    assert_eq!(evaulate("0n".into()), Ok(Value::from(BigInt::default())));
+    assert_eq!(
+        evaulate("-3r".into()),
+        Ok(Value::from(Rational::from(BigInt::from(-3))))
+    );
+
+    // Suffixed literals are still range checked
+    assert!(matches!(
+        evaulate("9223372036854775808i64".into()),
+        Err(Error::Overflow { .. })
+    ));
+}
+
+#[test]
+fn integer_suffixes_evaulate() {
+    let evaulate = |source| Value::try_from(Integer::parse(source).unwrap().1);
+
+    assert_eq!(
+        evaulate("255u8".into()),
+        Ok(Value::Integer(255, IntegerType::UInt8))
+    );
+    assert_eq!(
+        evaulate("-128i8".into()),
+        Ok(Value::Integer(-128, IntegerType::Int8))
+    );
+    assert_eq!(
+        evaulate("18_446_744_073_709_551_615u64".into()),
+        Ok(Value::Integer(u64::MAX.into(), IntegerType::UInt64))
+    );
+    assert_eq!(
+        evaulate("256u8".into()),
+        Err(Error::Overflow {
+            data_type: Type::Integer(IntegerType::UInt8),
+            detail: "integer literal with 3 digits in base 10 is out of range".to_string(),
+        })
+    );
+    assert!(matches!(
+        evaulate("-1u32".into()),
+        Err(Error::Overflow { .. })
+    ));
+}
@@ -0,0 +1,27 @@
+use super::super::Value;
+use crate::parser::prelude::*;
+
+impl From<literal::String> for Value<'_> {
+    fn from(value: literal::String) -> Self {
+        Self::String(value.0.into())
+    }
+}
+
+#[test]
+fn string_evaulates() {
+    for (source, expected) in [
+        ("\"\"", ""),
+        ("\"hello world\"", "hello world"),
+        ("\"line\\nbreak\\r\\0\"", "line\nbreak\r\0"),
+        ("\"\\\"quoted\\\" \\'\"", "\"quoted\" '"),
+        ("\"naïve ☃\"", "naïve ☃"),
+        ("r\"\\d+\\.\\d+\"", "\\d+\\.\\d+"),
+        (
+            "\"\"\"\n    first\n      second\n    \"\"\"",
+            "first\n  second",
+        ),
+    ] {
+        let (_, string) = literal::String::parse(source.into()).unwrap();
+        assert_eq!(Value::from(string), Value::String(expected.into()));
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use super::{big_int::decode_f64, BigInt, FloatType};
+
+/// An exact fraction.
+///
+/// Fractions are kept in lowest terms with a positive denominator, so that each number has
+/// exactly one representation and equal fractions compare equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    /// The fraction `numerator / denominator` in lowest terms, or `None` if the denominator is
+    /// zero
+    pub fn new(numerator: &BigInt, denominator: &BigInt) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+
+        let divisor = greatest_common_divisor(numerator.clone(), denominator.clone());
+        let numerator = numerator.checked_div(&divisor).unwrap_or_default();
+        let denominator = denominator.checked_div(&divisor).unwrap_or_default();
+
+        Some(if denominator.is_negative() {
+            Self {
+                numerator: -numerator,
+                denominator: -denominator,
+            }
+        } else {
+            Self {
+                numerator,
+                denominator,
+            }
+        })
+    }
+
+    /// The fraction equal to a float, or `None` for infinities and NaN
+    pub fn from_float(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let (negative, mantissa, exponent) = decode_f64(value);
+        let mantissa = BigInt::from(i128::from(mantissa));
+        let mantissa = if negative { -mantissa } else { mantissa };
+
+        let scale = BigInt::from(2).pow(exponent.unsigned_abs());
+        if exponent < 0 {
+            Self::new(&mantissa, &scale)
+        } else {
+            Some(Self::from(&mantissa * &scale))
+        }
+    }
+
+    pub const fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    pub const fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    /// The integer part of the fraction, rounding towards zero
+    pub fn trunc(&self) -> BigInt {
+        self.numerator
+            .checked_div(&self.denominator)
+            .unwrap_or_default()
+    }
+
+    /// One divided by the fraction, or `None` if it is zero
+    pub fn reciprocal(&self) -> Option<Self> {
+        Self::new(&self.denominator, &self.numerator)
+    }
+
+    /// Division, or `None` when dividing by zero
+    pub fn checked_div(&self, divisor: &Self) -> Option<Self> {
+        Self::new(
+            &(&self.numerator * &divisor.denominator),
+            &(&self.denominator * &divisor.numerator),
+        )
+    }
+
+    /// The remainder of division truncated towards zero, which takes the sign of the dividend
+    /// as it does for integers, or `None` when dividing by zero
+    pub fn checked_rem(&self, divisor: &Self) -> Option<Self> {
+        let quotient = Self::from(self.checked_div(divisor)?.trunc());
+        Some(self - &(divisor * &quotient))
+    }
+
+    pub fn pow(&self, exponent: u32) -> Self {
+        Self {
+            numerator: self.numerator.pow(exponent),
+            denominator: self.denominator.pow(exponent),
+        }
+    }
+
+    /// The nearest value of a float type to the fraction
+    pub fn to_float(&self, data_type: FloatType) -> f64 {
+        if self.numerator.is_zero() {
+            return 0.0;
+        }
+
+        let magnitude = if self.numerator.is_negative() {
+            -self.numerator.clone()
+        } else {
+            self.numerator.clone()
+        };
+        let value = data_type.round_ratio(&magnitude, &self.denominator);
+        if self.numerator.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+/// Euclid's algorithm, which gives a positive divisor unless both integers are zero
+fn greatest_common_divisor(mut a: BigInt, mut b: BigInt) -> BigInt {
+    while let Some(remainder) = a.checked_rem(&b) {
+        (a, b) = (b, remainder);
+    }
+    if a.is_negative() {
+        -a
+    } else {
+        a
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(value: BigInt) -> Self {
+        Self {
+            numerator: value,
+            denominator: BigInt::from(1),
+        }
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+
+    fn add(self, other: Self) -> Rational {
+        Rational::new(
+            &(&(&self.numerator * &other.denominator) + &(&other.numerator * &self.denominator)),
+            &(&self.denominator * &other.denominator),
+        )
+        .unwrap_or_default()
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Self) -> Rational {
+        self + &-other.clone()
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Self) -> Rational {
+        Rational::new(
+            &(&self.numerator * &other.numerator),
+            &(&self.denominator * &other.denominator),
+        )
+        .unwrap_or_default()
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+/// Zero, which the operators above fall back to, although their denominators are never zero
+impl Default for Rational {
+    fn default() -> Self {
+        Self::from(BigInt::default())
+    }
+}
+
+/// Denominators are positive, so fractions compare like their cross products
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Fractions are displayed as `numerator/denominator`, or just the numerator when they are
+/// whole numbers
+impl std::fmt::Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denominator == BigInt::from(1) {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[test]
+fn rationals_normalize() {
+    let fraction = |numerator, denominator| {
+        Rational::new(&BigInt::from(numerator), &BigInt::from(denominator)).unwrap()
+    };
+
+    assert_eq!(fraction(2, 4), fraction(1, 2));
+    assert_eq!(fraction(3, -6), fraction(-1, 2));
+    assert_eq!(fraction(-3, -6).to_string(), "1/2");
+    assert_eq!(fraction(0, -5), Rational::default());
+    assert_eq!(fraction(10, 5).to_string(), "2");
+    assert_eq!(Rational::new(&BigInt::from(1), &BigInt::default()), None);
+
+    assert_eq!(Rational::from_float(0.75), Some(fraction(3, 4)));
+    assert_eq!(Rational::from_float(-3.0), Some(fraction(-3, 1)));
+    assert_eq!(
+        Rational::from_float(0.1).map(|value| value.to_float(FloatType::Float64)),
+        Some(0.1)
+    );
+    assert_eq!(
+        Rational::from_float(f64::from_bits(1)).map(|value| value.denominator().bits()),
+        Some(1075)
+    );
+    assert_eq!(Rational::from_float(f64::NAN), None);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn rational_arithmetic() {
+    let fraction = |numerator, denominator| {
+        Rational::new(&BigInt::from(numerator), &BigInt::from(denominator)).unwrap()
+    };
+
+    assert_eq!(&fraction(1, 10) + &fraction(2, 10), fraction(3, 10));
+    assert_eq!(&fraction(1, 2) - &fraction(3, 4), fraction(-1, 4));
+    assert_eq!(&fraction(2, 3) * &fraction(9, 4), fraction(3, 2));
+    assert_eq!(
+        fraction(1, 3).checked_div(&fraction(-2, 3)),
+        Some(fraction(-1, 2))
+    );
+    assert_eq!(fraction(1, 3).checked_div(&Rational::default()), None);
+    assert_eq!(
+        fraction(-7, 2).checked_rem(&fraction(1, 1)),
+        Some(fraction(-1, 2))
+    );
+    assert_eq!(fraction(-7, 2).trunc(), BigInt::from(-3));
+    assert_eq!(fraction(-2, 3).pow(3), fraction(-8, 27));
+    assert_eq!(fraction(-2, 3).reciprocal(), Some(fraction(-3, 2)));
+    assert!(fraction(1, 3) < fraction(1, 2) && fraction(-1, 2) < fraction(-1, 3));
+    assert_eq!(fraction(1, 3).to_float(FloatType::Float64), 1.0 / 3.0);
+    assert_eq!(
+        fraction(-1, 3).to_float(FloatType::Float32),
+        f64::from(-1.0f32 / 3.0)
+    );
+}
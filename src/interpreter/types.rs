@@ -0,0 +1,385 @@
+use super::{BigInt, Error, Type};
+use crate::parser::{prelude::Span, types};
+
+impl Type {
+    /// The type with the given name, if there is one
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "Int8" => Some(Self::Integer(IntegerType::Int8)),
+            "Int16" => Some(Self::Integer(IntegerType::Int16)),
+            "Int32" => Some(Self::Integer(IntegerType::Int32)),
+            "Int64" => Some(Self::Integer(IntegerType::Int64)),
+            "UInt8" => Some(Self::Integer(IntegerType::UInt8)),
+            "UInt16" => Some(Self::Integer(IntegerType::UInt16)),
+            "UInt32" => Some(Self::Integer(IntegerType::UInt32)),
+            "UInt64" => Some(Self::Integer(IntegerType::UInt64)),
+            "BigInt" => Some(Self::BigInt),
+            "Rational" => Some(Self::Rational),
+            "Float32" => Some(Self::Float(FloatType::Float32)),
+            "Float64" => Some(Self::Float(FloatType::Float64)),
+            "String" => Some(Self::String),
+            "Boolean" => Some(Self::Boolean),
+            "Character" => Some(Self::Character),
+            _ => None,
+        }
+    }
+}
+
+/// A fixed-width integer type, which determines the range of values an integer can hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+}
+
+impl IntegerType {
+    /// The smallest value of the type
+    pub fn min(self) -> i128 {
+        match self {
+            Self::Int8 => i8::MIN.into(),
+            Self::Int16 => i16::MIN.into(),
+            Self::Int32 => i32::MIN.into(),
+            Self::Int64 => i64::MIN.into(),
+            Self::UInt8 | Self::UInt16 | Self::UInt32 | Self::UInt64 => 0,
+        }
+    }
+
+    /// The largest value of the type
+    pub fn max(self) -> i128 {
+        match self {
+            Self::Int8 => i8::MAX.into(),
+            Self::Int16 => i16::MAX.into(),
+            Self::Int32 => i32::MAX.into(),
+            Self::Int64 => i64::MAX.into(),
+            Self::UInt8 => u8::MAX.into(),
+            Self::UInt16 => u16::MAX.into(),
+            Self::UInt32 => u32::MAX.into(),
+            Self::UInt64 => u64::MAX.into(),
+        }
+    }
+
+    /// Whether the type can hold `value`
+    pub fn contains(self, value: i128) -> bool {
+        (self.min()..=self.max()).contains(&value)
+    }
+}
+
+impl std::fmt::Display for IntegerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A floating point type, which determines the precision a float is rounded to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatType {
+    Float32,
+    Float64,
+}
+
+impl FloatType {
+    /// Rounds `value` to the nearest value the type can hold
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            Self::Float32 => (value as f32).into(),
+            Self::Float64 => value,
+        }
+    }
+
+    /// The precision in bits of the type, and the smallest and largest exponents of its normal
+    /// numbers
+    pub const fn parameters(self) -> (i32, i32, i32) {
+        #[allow(clippy::cast_possible_wrap)]
+        match self {
+            Self::Float32 => (
+                f32::MANTISSA_DIGITS as i32,
+                f32::MIN_EXP - 1,
+                f32::MAX_EXP - 1,
+            ),
+            Self::Float64 => (
+                f64::MANTISSA_DIGITS as i32,
+                f64::MIN_EXP - 1,
+                f64::MAX_EXP - 1,
+            ),
+        }
+    }
+
+    /// The nearest value of the type to a positive ratio of integers, rounding ties to even
+    #[allow(clippy::cast_precision_loss)]
+    pub fn round_ratio(self, numerator: &BigInt, denominator: &BigInt) -> f64 {
+        let (precision, min_exponent, max_exponent) = self.parameters();
+        let bits = |integer: &BigInt| i64::try_from(integer.bits()).unwrap_or(i64::MAX);
+
+        // Scale the ratio by a power of two so that its integer part has two or three bits more
+        // than the precision, leaving a rounding bit and a bit to tell whether the rest is zero
+        let shift = i64::from(precision) + 2 - (bits(numerator) - bits(denominator));
+        let scale = BigInt::from(2).pow(u32::try_from(shift.unsigned_abs()).unwrap_or(u32::MAX));
+        let (numerator, denominator) = if shift < 0 {
+            (numerator.clone(), denominator * &scale)
+        } else {
+            (numerator * &scale, denominator.clone())
+        };
+
+        let quotient = numerator
+            .checked_div(&denominator)
+            .and_then(|quotient| quotient.to_i128())
+            .and_then(|quotient| u64::try_from(quotient).ok())
+            .unwrap_or_default();
+        let inexact = numerator
+            .checked_rem(&denominator)
+            .is_some_and(|remainder| !remainder.is_zero());
+
+        // Subnormal numbers have fewer bits of precision, as their exponent cannot go any lower
+        let length = i64::from(u64::BITS - quotient.leading_zeros());
+        let exponent = length - 1 - shift;
+        let discarded = length - i64::from(precision) + (i64::from(min_exponent) - exponent).max(0);
+        if discarded > length {
+            return 0.0;
+        }
+
+        let discarded = u32::try_from(discarded).unwrap_or_default();
+        let mut mantissa = quotient >> discarded;
+        let remainder = quotient & ((1 << discarded) - 1);
+        let half = 1 << (discarded - 1);
+        if remainder > half || remainder == half && (inexact || mantissa & 1 == 1) {
+            mantissa += 1;
+        }
+
+        // Rounding up may have carried into a higher exponent
+        let exponent = i64::from(discarded) - shift;
+        if i64::from(u64::BITS - mantissa.leading_zeros()) - 1 + exponent > i64::from(max_exponent)
+        {
+            return f64::INFINITY;
+        }
+
+        // Scale in two steps, so that neither power of two overflows or underflows by itself
+        let exponent = i32::try_from(exponent).unwrap_or_default();
+        mantissa as f64 * 2f64.powi(exponent / 2) * 2f64.powi(exponent - exponent / 2)
+    }
+}
+
+impl std::fmt::Display for FloatType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Types are displayed as they are written in annotations
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(data_type) => write!(f, "{data_type}"),
+            Self::Float(data_type) => write!(f, "{data_type}"),
+            Self::BigInt => write!(f, "BigInt"),
+            Self::Rational => write!(f, "Rational"),
+            Self::String => write!(f, "String"),
+            Self::Boolean => write!(f, "Boolean"),
+            Self::Character => write!(f, "Character"),
+            Self::Function {
+                return_type,
+                parameters,
+            } => {
+                write!(f, "fn(")?;
+                for (index, parameter) in parameters.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{parameter}")?;
+                }
+                write!(f, ") -> {return_type}")
+            }
+            Self::List(element) => write!(f, "[{element}]"),
+            Self::Tuple(elements) => {
+                write!(f, "(")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                // A single type in parentheses would be that type rather than a tuple
+                if elements.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Self::Variable(index) => match u8::try_from(*index) {
+                Ok(index @ 0..=25) => write!(f, "'{}", char::from(b'a' + index)),
+                _ => write!(f, "'t{index}"),
+            },
+            Self::Unknown => write!(f, "_"),
+        }
+    }
+}
+
+impl Type {
+    /// Resolves a type annotation to the type it names. If any name in it is not a type,
+    /// `unknown` is called with each such name and the annotation does not resolve.
+    pub fn from_annotation(
+        annotation: &types::Type,
+        unknown: &mut impl FnMut(&Span),
+    ) -> Option<Self> {
+        match annotation {
+            types::Type::Named(identifier) => Self::named(identifier.0.fragment()).or_else(|| {
+                unknown(&identifier.0);
+                None
+            }),
+            types::Type::Function {
+                parameters,
+                return_type,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|parameter| Self::from_annotation(parameter, unknown))
+                    .collect();
+                let return_type = Self::from_annotation(return_type, unknown);
+
+                Some(Self::Function {
+                    return_type: Box::new(return_type?),
+                    parameters: parameters.into_iter().collect::<Option<_>>()?,
+                })
+            }
+            types::Type::List(element) => Some(Self::List(Box::new(Self::from_annotation(
+                element, unknown,
+            )?))),
+            types::Type::Tuple(elements) => {
+                let elements: Vec<_> = elements
+                    .iter()
+                    .map(|element| Self::from_annotation(element, unknown))
+                    .collect();
+                Some(Self::Tuple(elements.into_iter().collect::<Option<_>>()?))
+            }
+            types::Type::Generic { name, arguments } => {
+                for argument in arguments {
+                    Self::from_annotation(argument, unknown);
+                }
+                // There are no generic types yet, so the name is never one
+                unknown(&name.0);
+                None
+            }
+        }
+    }
+}
+
+/// Resolves a type annotation to the type it names, failing at the first unknown name in it
+impl TryFrom<&types::Type<'_>> for Type {
+    type Error = Error;
+
+    fn try_from(annotation: &types::Type) -> Result<Self, Self::Error> {
+        let mut error = None;
+        let data_type = Self::from_annotation(annotation, &mut |name| {
+            error.get_or_insert_with(|| Error::UnknownType {
+                name: name.to_string(),
+                line: name.location_line(),
+                column: name.get_utf8_column(),
+            });
+        });
+        data_type.ok_or_else(|| error.expect("annotations only fail to resolve at unknown names"))
+    }
+}
+
+#[test]
+fn types_display() {
+    assert_eq!(Type::Integer(IntegerType::Int64).to_string(), "Int64");
+    assert_eq!(Type::Integer(IntegerType::UInt8).to_string(), "UInt8");
+    assert_eq!(Type::Float(FloatType::Float32).to_string(), "Float32");
+    assert_eq!(
+        Type::Function {
+            return_type: Box::new(Type::Function {
+                return_type: Box::new(Type::Boolean),
+                parameters: Vec::new(),
+            }),
+            parameters: vec![Type::Float(FloatType::Float64), Type::Character],
+        }
+        .to_string(),
+        "fn(Float64, Character) -> fn() -> Boolean"
+    );
+    assert_eq!(
+        Type::Function {
+            return_type: Box::new(Type::Variable(27)),
+            parameters: vec![Type::Variable(0), Type::Variable(1)],
+        }
+        .to_string(),
+        "fn('a, 'b) -> 't27"
+    );
+    assert_eq!(
+        Type::Function {
+            return_type: Box::new(Type::Unknown),
+            parameters: vec![Type::Unknown, Type::Boolean],
+        }
+        .to_string(),
+        "fn(_, Boolean) -> _"
+    );
+    assert_eq!(
+        Type::List(Box::new(Type::Tuple(vec![
+            Type::Integer(IntegerType::Int64),
+            Type::String
+        ])))
+        .to_string(),
+        "[(Int64, String)]"
+    );
+    assert_eq!(
+        Type::Tuple(vec![Type::Tuple(Vec::new())]).to_string(),
+        "((),)"
+    );
+}
+
+#[test]
+fn types_resolve() {
+    let resolve = |source| Type::try_from(&types::Type::parse(source).unwrap().1);
+
+    assert_eq!(
+        resolve("Int64".into()),
+        Ok(Type::Integer(IntegerType::Int64))
+    );
+    assert_eq!(
+        resolve("UInt16".into()),
+        Ok(Type::Integer(IntegerType::UInt16))
+    );
+    assert_eq!(resolve("BigInt".into()), Ok(Type::BigInt));
+    assert_eq!(resolve("Rational".into()), Ok(Type::Rational));
+    assert_eq!(
+        resolve("Float32".into()),
+        Ok(Type::Float(FloatType::Float32))
+    );
+    assert_eq!(
+        resolve("fn(Float64, Character) -> fn() -> Boolean".into()),
+        Ok(Type::Function {
+            return_type: Box::new(Type::Function {
+                return_type: Box::new(Type::Boolean),
+                parameters: Vec::new(),
+            }),
+            parameters: vec![Type::Float(FloatType::Float64), Type::Character],
+        })
+    );
+    assert_eq!(
+        resolve("[(Character, [Boolean])]".into()),
+        Ok(Type::List(Box::new(Type::Tuple(vec![
+            Type::Character,
+            Type::List(Box::new(Type::Boolean))
+        ]))))
+    );
+    assert_eq!(
+        resolve("Option<Int64>".into()),
+        Err(Error::UnknownType {
+            name: "Option".to_string(),
+            line: 1,
+            column: 1,
+        })
+    );
+    assert_eq!(
+        resolve("fn(Int64, Int46) -> String".into()),
+        Err(Error::UnknownType {
+            name: "Int46".to_string(),
+            line: 1,
+            column: 11,
+        })
+    );
+}
@@ -0,0 +1,316 @@
+use std::{
+    cell::Cell,
+    rc::{Rc, Weak},
+};
+
+use super::{
+    BigInt, Error, Evaluates, FloatType, IntegerType, Rational, Scope, Type, MAX_CALL_DEPTH,
+};
+use crate::parser::{function::Function, types};
+
+/// A value produced by evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    /// An integer of any integer type, which is always within the range of that type
+    Integer(i128, IntegerType),
+    /// A float of any float type, which is always rounded to the precision of that type
+    Float(f64, FloatType),
+    /// An integer of any size, for arithmetic that would overflow a fixed-width type
+    BigInt(BigInt),
+    /// An exact fraction, for arithmetic that cannot tolerate rounding
+    Rational(Rational),
+    Boolean(bool),
+    Character(char),
+    /// Strings are immutable, so clones share the same allocation
+    String(Rc<str>),
+    Function(Rc<Closure<'a>>),
+}
+
+impl Value<'_> {
+    /// The type of the value
+    pub fn data_type(&self) -> Type {
+        match self {
+            Self::Integer(_, data_type) => Type::Integer(*data_type),
+            Self::Float(_, data_type) => Type::Float(*data_type),
+            Self::BigInt(_) => Type::BigInt,
+            Self::Rational(_) => Type::Rational,
+            Self::Boolean(_) => Type::Boolean,
+            Self::Character(_) => Type::Character,
+            Self::String(_) => Type::String,
+            Self::Function(closure) => closure.data_type.clone(),
+        }
+    }
+}
+
+impl From<i64> for Value<'_> {
+    fn from(value: i64) -> Self {
+        Self::Integer(value.into(), IntegerType::Int64)
+    }
+}
+
+impl From<f64> for Value<'_> {
+    fn from(value: f64) -> Self {
+        Self::Float(value, FloatType::Float64)
+    }
+}
+
+impl From<BigInt> for Value<'_> {
+    fn from(value: BigInt) -> Self {
+        Self::BigInt(value)
+    }
+}
+
+impl From<Rational> for Value<'_> {
+    fn from(value: Rational) -> Self {
+        Self::Rational(value)
+    }
+}
+
+impl From<bool> for Value<'_> {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<char> for Value<'_> {
+    fn from(value: char) -> Self {
+        Self::Character(value)
+    }
+}
+
+impl From<&str> for Value<'_> {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for Value<'_> {
+    fn from(value: String) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl std::fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(value, _) => write!(f, "{value}"),
+            Self::Float(value, FloatType::Float32) => {
+                // Display the shortest float that rounds to the same `f32`
+                #[allow(clippy::cast_possible_truncation)]
+                let value = *value as f32;
+                write!(f, "{value:?}")
+            }
+            Self::Float(value, FloatType::Float64) => write!(f, "{value:?}"),
+            Self::BigInt(value) => write!(f, "{value}"),
+            Self::Rational(value) => write!(f, "{value}"),
+            Self::Boolean(value) => write!(f, "{value}"),
+            Self::Character(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value}"),
+            Self::Function(_) => write!(f, "<{}>", self.data_type()),
+        }
+    }
+}
+
+thread_local! {
+    /// How many closure calls are being evaluated on this thread
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A function value, along with the scope it was defined in
+pub struct Closure<'a> {
+    pub function: Function<'a>,
+    pub data_type: Type,
+    scope: Captured<'a>,
+}
+
+/// How a closure refers to the scope it was defined in
+#[derive(Debug, Clone)]
+enum Captured<'a> {
+    Scope(Rc<Scope<'a>>),
+    /// The `let rec` scope the closure is bound in. The scope owns the closure, so a strong
+    /// reference back to it would be a cycle that is never freed.
+    Recursive(Weak<Scope<'a>>),
+}
+
+impl<'a> Closure<'a> {
+    /// Captures the scope a function is defined in, resolving its type annotations. Types
+    /// that are not annotated are unknown, as only the type checker infers them.
+    pub fn new(function: Function<'a>, scope: Rc<Scope<'a>>) -> Result<Self, Error> {
+        let resolve =
+            |annotation: Option<&types::Type>| annotation.map_or(Ok(Type::Unknown), Type::try_from);
+
+        let data_type = Type::Function {
+            parameters: function
+                .parameters
+                .0
+                .iter()
+                .map(|(parameter_type, _)| resolve(parameter_type.as_ref()))
+                .collect::<Result<_, _>>()?,
+            return_type: Box::new(resolve(function.return_type.as_ref())?),
+        };
+
+        Ok(Self {
+            function,
+            data_type,
+            scope: Captured::Scope(scope),
+        })
+    }
+
+    /// The scope the closure was defined in
+    pub fn scope(&self) -> Rc<Scope<'a>> {
+        match &self.scope {
+            Captured::Scope(scope) => Rc::clone(scope),
+            Captured::Recursive(scope) => scope
+                .upgrade()
+                .expect("recursive closures are only reachable while their scope is alive"),
+        }
+    }
+
+    /// The closure to bind in the `let rec` scope `scope`, which only refers weakly to that
+    /// scope if it was defined in it
+    pub fn bind_recursively(closure: &Rc<Self>, scope: &Rc<Scope<'a>>) -> Rc<Self> {
+        match &closure.scope {
+            Captured::Scope(captured) if Rc::ptr_eq(captured, scope) => Rc::new(Self {
+                function: closure.function.clone(),
+                data_type: closure.data_type.clone(),
+                scope: Captured::Recursive(Rc::downgrade(scope)),
+            }),
+            _ => Rc::clone(closure),
+        }
+    }
+
+    /// The closure with a strong reference to its scope, for use outside of the scope that
+    /// owns it
+    pub fn unbind(closure: &Rc<Self>) -> Rc<Self> {
+        match closure.scope {
+            Captured::Scope(_) => Rc::clone(closure),
+            Captured::Recursive(_) => Rc::new(Self {
+                function: closure.function.clone(),
+                data_type: closure.data_type.clone(),
+                scope: Captured::Scope(closure.scope()),
+            }),
+        }
+    }
+
+    /// Evaluates the function body in a new scope, binding each parameter to its argument. A call
+    /// inside [`MAX_CALL_DEPTH`] other calls fails instead.
+    pub fn call(&self, arguments: Vec<Value<'a>>) -> Result<Value<'a>, Error> {
+        let parameters = &self.function.parameters.0;
+        if parameters.len() != arguments.len() {
+            return Err(Error::ArgumentCount {
+                expected: parameters.len(),
+                found: arguments.len(),
+            });
+        }
+
+        let scope = Scope::new(
+            parameters
+                .iter()
+                .map(|(_, identifier)| *identifier.0.fragment())
+                .zip(arguments),
+            Some(self.scope()),
+        );
+
+        let depth = DEPTH.get();
+        if depth >= MAX_CALL_DEPTH {
+            return Err(Error::RecursionLimit);
+        }
+        DEPTH.set(depth + 1);
+        let result = self.function.body.evaulate(&Rc::new(scope));
+        DEPTH.set(depth);
+        result
+    }
+}
+
+/// Closures are compared by identity, as function bodies cannot be compared meaningfully. A
+/// closure is the same as another if it was created from the same function in the same scope.
+impl PartialEq for Closure<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        let scope = |closure: &Self| match &closure.scope {
+            Captured::Scope(scope) => Rc::as_ptr(scope),
+            Captured::Recursive(scope) => scope.as_ptr(),
+        };
+        self.function.span == other.function.span && scope(self) == scope(other)
+    }
+}
+
+/// The captured scope is omitted, as it may contain the closure itself
+impl std::fmt::Debug for Closure<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Closure")
+            .field("function", &self.function)
+            .field("data_type", &self.data_type)
+            .finish_non_exhaustive()
+    }
+}
+
+#[test]
+fn deep_recursion_fails() {
+    use super::{test, STACK_SIZE};
+
+    let count =
+        |n| format!("let rec count = fn(n) {{ if n == 0 then 0 else count(n - 1) }} in count({n})");
+    let results = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            [
+                count(10_000),
+                count(MAX_CALL_DEPTH),
+                count(MAX_CALL_DEPTH - 1),
+            ]
+            .map(|source| test::evaulate(&source).map(|value| value.to_string()))
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    // A failed call does not count towards the depth of later calls
+    assert_eq!(
+        results,
+        [
+            Err(Error::RecursionLimit),
+            Err(Error::RecursionLimit),
+            Ok("0".to_string())
+        ]
+    );
+}
+
+#[test]
+fn value_converts() {
+    assert_eq!(Value::from(1), Value::Integer(1, IntegerType::Int64));
+    assert_eq!(Value::from(0.5), Value::Float(0.5, FloatType::Float64));
+    assert_eq!(Value::from(BigInt::from(1)).data_type(), Type::BigInt);
+    assert_eq!(
+        Value::from(Rational::from(BigInt::from(1))).data_type(),
+        Type::Rational
+    );
+    assert_eq!(Value::from(true), Value::Boolean(true));
+    assert_eq!(Value::from('a'), Value::Character('a'));
+    assert_eq!(Value::from("abc"), Value::String("abc".into()));
+    assert_eq!(Value::from(String::from("abc")).data_type(), Type::String);
+}
+
+#[test]
+fn value_displays() {
+    assert_eq!(Value::from(-3).to_string(), "-3");
+    assert_eq!(Value::from(1.0).to_string(), "1.0");
+    assert_eq!(
+        Value::Float(FloatType::Float32.round(0.1), FloatType::Float32).to_string(),
+        "0.1"
+    );
+    assert_eq!(
+        Value::Integer(u64::MAX.into(), IntegerType::UInt64).to_string(),
+        "18446744073709551615"
+    );
+    assert_eq!(
+        Value::from(BigInt::from(i128::MIN)).to_string(),
+        i128::MIN.to_string()
+    );
+    assert_eq!(
+        Value::from(Rational::new(&BigInt::from(-6), &BigInt::from(4)).unwrap()).to_string(),
+        "-3/2"
+    );
+    assert_eq!(Value::from(false).to_string(), "false");
+    assert_eq!(Value::from('x').to_string(), "x");
+    assert_eq!(Value::from("text").to_string(), "text");
+}
@@ -1,19 +1,46 @@
 #![warn(clippy::pedantic, clippy::nursery)]
 
+use std::rc::Rc;
+
 use parser::prelude::*;
 
-use crate::interpreter::{Evaluates, Scope};
+use crate::interpreter::Evaluates;
 
+mod checker;
 mod interpreter;
 mod parser;
 
 fn main() {
-    // println!(
-    //     "{:?}",
-    //     Expression::parse(include_str!("input.txt").into())
-    //         .unwrap()
-    //         .1
-    //         .evaulate(Scope::default())
-    // );
-    println!("{:#?}", Expression::parse(include_str!("input.txt").into()))
+    // Recursion needs more stack than the main thread has to reach the interpreter's call limit
+    std::thread::Builder::new()
+        .stack_size(interpreter::STACK_SIZE)
+        .spawn(run)
+        .expect("the interpreter thread should start")
+        .join()
+        .expect("the interpreter should not panic");
+}
+
+fn run() {
+    match Expression::parse(include_str!("input.txt").into()) {
+        Ok((rest, _)) if !rest.is_empty() => eprintln!(
+            "{}:{}: Expected the end of the input, found `{}`",
+            rest.location_line(),
+            rest.get_utf8_column(),
+            rest.fragment()
+        ),
+        Ok((_, expression)) => {
+            if let Err(errors) = checker::check(&expression) {
+                for error in errors {
+                    eprintln!("{error}");
+                }
+                return;
+            }
+            match expression.evaulate(&Rc::default()) {
+                Ok(value) => println!("{value}"),
+                Err(error) => eprintln!("{error}"),
+            }
+        }
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => eprintln!("{error}"),
+        Err(nom::Err::Incomplete(_)) => eprintln!("Unexpected end of input"),
+    }
 }
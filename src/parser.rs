@@ -1,8 +1,12 @@
+pub mod conditional;
+pub mod error;
 pub mod expression;
 pub mod function;
 pub mod identifier;
+pub mod interpolation;
 pub mod let_in;
 pub mod literal;
+pub mod separator;
 pub mod types;
 pub mod whitespace;
 
@@ -10,37 +14,42 @@ pub mod prelude {
     #[cfg(test)]
     pub use super::test;
     pub use super::{
-        expression::{self, Expression},
+        conditional::Conditional,
+        error,
+        expression::Expression,
         function::Function,
         identifier::Identifier,
+        interpolation::Interpolation,
         let_in::LetIn,
         literal::{self, number, Literal},
+        separator,
         types::Type,
         whitespace,
     };
     pub use nom::{
         branch::alt,
-        bytes::complete::{tag, take_until, take_while},
-        character::complete::{multispace1, one_of, satisfy},
-        combinator::{complete, consumed, not, opt, value},
+        bytes::complete::{tag, take_until, take_while, take_while_m_n},
+        character::complete::{line_ending, multispace1, one_of, satisfy},
+        combinator::{complete, consumed, cut, map_opt, not, opt, value},
         error::context,
-        multi::{many0, many1, separated_list0},
-        sequence::{delimited, pair, terminated, tuple},
-        IResult, Parser,
+        multi::{many0, many1, separated_list0, separated_list1},
+        sequence::{delimited, pair, preceded, terminated, tuple},
+        Parser,
     };
     pub use nom_locate::LocatedSpan;
 
     pub type Span<'a> = LocatedSpan<&'a str>;
+
+    pub type IResult<I, O, E = error::Error<I>> = nom::IResult<I, O, E>;
 }
 
 #[cfg(test)]
 pub mod test {
     use super::prelude::*;
-    use nom::{error::Error, IResult};
 
     pub fn strip_span<O>(result: IResult<Span, O>) -> IResult<String, O> {
         result
             .map(|(span, result)| (span.to_string(), result))
-            .map_err(|err| err.map(|err| Error::new(err.input.to_string(), err.code)))
+            .map_err(|err| err.map(|err| err.map_input(|input| input.to_string())))
     }
 }
@@ -0,0 +1,120 @@
+use super::prelude::*;
+
+/// A conditional expression, for example
+///
+/// ```!
+/// if n == 0 then 1 else n * factorial(n - 1)
+/// ```
+///
+/// Only the branch that is taken is evaluated. There is no `if` without an `else`, as the
+/// expression has to have a value either way, but an `else` can be followed by another `if`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conditional<'a> {
+    pub condition: Box<Expression<'a>>,
+    pub consequent: Box<Expression<'a>>,
+    pub alternative: Box<Expression<'a>>,
+    /// The `if` keyword as it appears in the source
+    pub span: Span<'a>,
+}
+
+impl<'a> Conditional<'a> {
+    /// Parses a conditional. As `if` cannot be an identifier, once it is found the rest of the
+    /// conditional has to follow.
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
+        let (input, span) = keyword("if")(input)?;
+        let (input, condition) = cut(Expression::parse)(input)?;
+        let (input, _) = cut(keyword("then"))(input)?;
+        let (input, consequent) = cut(Expression::parse)(input)?;
+        let (input, _) = cut(keyword("else"))(input)?;
+        let (input, alternative) = cut(Expression::parse)(input)?;
+
+        Ok((
+            input,
+            Self {
+                condition: Box::new(condition),
+                consequent: Box::new(consequent),
+                alternative: Box::new(alternative),
+                span,
+            },
+        ))
+    }
+}
+
+/// Parses a keyword that is not just the start of a longer identifier, such as the `if` in
+/// `iffy`
+fn keyword<'a>(keyword: &'static str) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Span<'a>> {
+    delimited(
+        whitespace::optional,
+        terminated(
+            tag(keyword),
+            not(satisfy(|character: char| {
+                character.is_alphanumeric() || character == '_'
+            })),
+        ),
+        whitespace::optional,
+    )
+}
+
+#[test]
+fn conditional_parses() {
+    let integer = |digit| {
+        Expression::Literal(Literal::Integer(literal::Integer {
+            base: number::Base::Decimal,
+            digits: vec![digit],
+            sign: number::Sign::Positive,
+            suffix: None,
+        }))
+    };
+
+    assert_eq!(
+        test::strip_span(Conditional::parse("if true then 1 else 2".into())),
+        Ok((
+            String::new(),
+            Conditional {
+                condition: Box::new(Expression::Literal(Literal::Boolean(literal::Boolean(
+                    true
+                )))),
+                consequent: Box::new(integer(1)),
+                alternative: Box::new(integer(2)),
+                span: Span::new("if"),
+            }
+        ))
+    );
+
+    // The alternative extends as far as it can, so it can be another conditional
+    let (input, conditional) =
+        Conditional::parse("if(a)then 1 else if b then 2 else 3 + x;".into()).unwrap();
+    assert_eq!(input.to_string(), ";");
+    assert!(matches!(*conditional.condition, Expression::Identifier(_)));
+    assert!(matches!(
+        *conditional.alternative,
+        Expression::Conditional(Conditional { ref alternative, .. })
+            if matches!(**alternative, Expression::Binary(_))
+    ));
+}
+
+#[test]
+fn incomplete_conditionals_fail() {
+    assert_eq!(
+        test::strip_span(Conditional::parse("if x then 1 x".into())),
+        Err(nom::Err::Failure(error::Error::new(
+            "x".to_string(),
+            nom::error::ErrorKind::Tag
+        )))
+    );
+    assert_eq!(
+        test::strip_span(Conditional::parse("if x else 2".into())),
+        Err(nom::Err::Failure(error::Error::new(
+            "else 2".to_string(),
+            nom::error::ErrorKind::Tag
+        )))
+    );
+    // An identifier that starts with `if` is not a conditional
+    assert_eq!(
+        test::strip_span(Conditional::parse("iffy then 1 else 2".into())),
+        Err(nom::Err::Error(error::Error::new(
+            "fy then 1 else 2".to_string(),
+            nom::error::ErrorKind::Not
+        )))
+    );
+}
@@ -0,0 +1,97 @@
+use super::prelude::*;
+
+/// An error from parsing, at the input that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<I> {
+    pub input: I,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input did not match what a nom combinator expected
+    Nom(nom::error::ErrorKind),
+    /// A backslash did not start a valid escape sequence
+    Escape { sequence: String },
+    /// Two items of a list, such as `arguments`, were not separated by a comma
+    Separator { between: &'static str },
+}
+
+impl<I> Error<I> {
+    pub const fn new(input: I, code: nom::error::ErrorKind) -> Self {
+        Self {
+            input,
+            kind: ErrorKind::Nom(code),
+        }
+    }
+
+    pub fn map_input<T>(self, f: impl FnOnce(I) -> T) -> Error<T> {
+        Error {
+            input: f(self.input),
+            kind: self.kind,
+        }
+    }
+}
+
+impl<I> nom::error::ParseError<I> for Error<I> {
+    fn from_error_kind(input: I, kind: nom::error::ErrorKind) -> Self {
+        Self::new(input, kind)
+    }
+
+    fn append(_: I, _: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<I> nom::error::ContextError<I> for Error<I> {}
+
+impl std::fmt::Display for Error<Span<'_>> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: ",
+            self.input.location_line(),
+            self.input.get_utf8_column()
+        )?;
+        match &self.kind {
+            ErrorKind::Nom(_) => match self.input.split_whitespace().next() {
+                Some(unexpected) => write!(f, "Unexpected `{unexpected}`"),
+                None => write!(f, "Unexpected end of input"),
+            },
+            ErrorKind::Escape { sequence } => write!(f, "Invalid escape sequence `{sequence}`"),
+            ErrorKind::Separator { between } => write!(f, "Expected `,` between {between}"),
+        }
+    }
+}
+
+#[test]
+fn errors_display() {
+    let input = Span::new("let x = 1,\n  y = ) in x");
+    let (input, _) = take_until::<_, _, Error<Span>>(")")(input).unwrap();
+    assert_eq!(
+        Error::new(input, nom::error::ErrorKind::Tag).to_string(),
+        "2:7: Unexpected `)`"
+    );
+
+    let (input, _) = take_while::<_, _, Error<Span>>(|_| true)(input).unwrap();
+    assert_eq!(
+        Error::new(input, nom::error::ErrorKind::Eof).to_string(),
+        "2:13: Unexpected end of input"
+    );
+
+    let error = Error {
+        input: Span::new("\\q'"),
+        kind: ErrorKind::Escape {
+            sequence: "\\q".to_string(),
+        },
+    };
+    assert_eq!(error.to_string(), "1:1: Invalid escape sequence `\\q`");
+
+    let error = Error {
+        input: Span::new("2)"),
+        kind: ErrorKind::Separator {
+            between: "arguments",
+        },
+    };
+    assert_eq!(error.to_string(), "1:1: Expected `,` between arguments");
+}
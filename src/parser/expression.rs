@@ -13,12 +13,14 @@ pub enum Expression<'a> {
     Unary(Unary<'a>),
     Identifier(Identifier<'a>),
     LetIn(LetIn<'a>),
+    Conditional(Conditional<'a>),
     Function(Function<'a>),
+    Interpolation(Interpolation<'a>),
 }
 
 impl<'a> Expression<'a> {
     /// Parse an arbitary expression
-    pub fn parse(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
         delimited(
             whitespace::optional,
             context("binary operations", Binary::parse),
@@ -27,7 +29,7 @@ impl<'a> Expression<'a> {
     }
 
     /// Parse all non-binary terms (e.g. literals and identifiers)
-    pub fn parse_term(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse_term(input: Span<'a>) -> IResult<Span<'a>, Self> {
         delimited(
             whitespace::optional,
             context("unary operations", Unary::parse),
@@ -36,12 +38,14 @@ impl<'a> Expression<'a> {
     }
 
     /// Parse all 'atoms' (e.g. literals and identifiers)
-    pub fn parse_atom(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse_atom(input: Span<'a>) -> IResult<Span<'a>, Self> {
         delimited(
             whitespace::optional,
             alt((
                 context("let-in", LetIn::parse.map(Self::LetIn)),
+                context("conditional", Conditional::parse.map(Self::Conditional)),
                 context("function", Function::parse.map(Self::Function)),
+                context("interpolation", Interpolation::parse.map(Self::Interpolation)),
                 context("literal", Literal::parse.map(Self::Literal)),
                 context("group", delimited(tag("("), Self::parse, tag(")"))),
                 context("identifier", Identifier::parse.map(Self::Identifier)),
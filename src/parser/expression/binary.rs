@@ -6,9 +6,11 @@ pub struct Binary<'a> {
     pub left: Box<Expression<'a>>,
     pub operator: Operator,
     pub right: Box<Expression<'a>>,
+    /// The operator as it appears in the source
+    pub span: Span<'a>,
 }
 
-impl<'a> Binary<'a> {
+impl Binary<'_> {
     pub fn parse(input: Span) -> IResult<Span, Expression> {
         let (input, terms) = Terms::parse(input)?;
         Ok((input, terms.reduce()))
@@ -55,20 +57,41 @@ impl Operator {
         ))(input)
     }
 
-    /// The ability of a operator to 'bind' to a term
+    /// The operator as it is written
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Exponent => "**",
+            Self::Modulo => "%",
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::Xor => "^",
+            Self::Equal => "==",
+            Self::LessThan => "<",
+            Self::LessThanOrEqual => "<=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanOrEqual => ">=",
+            Self::NotEqual => "!=",
+        }
+    }
+
+    /// The ability of a operator to 'bind' to a term, where higher powers bind more tightly
     pub const fn binding_powers(&self) -> (u8, u8) {
         match self {
-            Self::Add | Self::Subtract => (10, 15),
-            Self::Multiply | Self::Divide | Self::Modulo => (20, 25),
-            Self::Exponent => (35, 30),
+            Self::Or => (10, 15),
+            Self::And => (20, 25),
+            Self::Xor => (30, 35),
+            Self::Equal | Self::NotEqual => (40, 45),
             Self::LessThan
             | Self::LessThanOrEqual
             | Self::GreaterThan
-            | Self::GreaterThanOrEqual => (40, 45),
-            Self::Equal | Self::NotEqual => (50, 55),
-            Self::Xor => (60, 65),
-            Self::And => (70, 75),
-            Self::Or => (80, 85),
+            | Self::GreaterThanOrEqual => (50, 55),
+            Self::Add | Self::Subtract => (60, 65),
+            Self::Multiply | Self::Divide | Self::Modulo => (70, 75),
+            Self::Exponent => (85, 80),
         }
     }
 }
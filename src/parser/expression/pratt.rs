@@ -8,15 +8,24 @@ pub struct Terms<'a> {
     // The first term in a sequence of binary expressions (e.g. `1` in `1 + 2 * 3`)
     left_term: Expression<'a>,
     /// The operators and expressions to the right of `left_term`. stored in the reverse order to that which they appear in the expression
-    right: Vec<(Operator, Expression<'a>)>,
+    right: Vec<(Span<'a>, Operator, Expression<'a>)>,
 }
 
 impl<'a> Terms<'a> {
-    pub fn parse(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
         let (input, left_term) = Expression::parse_term(input)?;
-        let (input, right) = many0(complete(pair(Operator::parse, Expression::parse_term)))
-            .map(|terms| terms.into_iter().rev().collect())
-            .parse(input)?;
+        let (input, right) = many0(complete(pair(
+            consumed(Operator::parse),
+            Expression::parse_term,
+        )))
+        .map(|terms| {
+            terms
+                .into_iter()
+                .rev()
+                .map(|((span, operator), term)| (span, operator, term))
+                .collect()
+        })
+        .parse(input)?;
 
         Ok((input, Self { left_term, right }))
     }
@@ -24,29 +33,31 @@ impl<'a> Terms<'a> {
     /// Reduces terms to one expression
     pub fn reduce(self) -> Expression<'a> {
         let Self {
-            mut left_term,
+            left_term,
             mut right,
         } = self;
 
-        while let Some((operator, right_term)) = right.pop() {
-            let left_bp = operator.binding_powers().1;
-            let right_bp = right.last().map_or(255, |(op, _)| op.binding_powers().0);
+        Self::reduce_from(left_term, &mut right, 0)
+    }
 
-            if left_bp < right_bp {
-                return Expression::Binary(Binary {
-                    left: Box::new(left_term),
-                    operator,
-                    right: Box::new(Self::reduce(Self {
-                        left_term: right_term,
-                        right,
-                    })),
-                });
+    /// Folds operators into `left_term` while they bind at least as tightly as `min_bp`
+    fn reduce_from(
+        mut left_term: Expression<'a>,
+        right: &mut Vec<(Span<'a>, Operator, Expression<'a>)>,
+        min_bp: u8,
+    ) -> Expression<'a> {
+        while let Some((_, operator, _)) = right.last() {
+            let (left_bp, right_bp) = operator.binding_powers();
+            if left_bp < min_bp {
+                break;
             }
 
+            let (span, operator, right_term) = right.pop().unwrap();
             left_term = Expression::Binary(Binary {
                 left: Box::new(left_term),
                 operator,
-                right: Box::new(right_term),
+                right: Box::new(Self::reduce_from(right_term, right, right_bp)),
+                span,
             });
         }
 
@@ -64,10 +75,12 @@ fn terms_parse() {
                 left_term: Expression::Literal(Literal::Character(literal::Character('a'))),
                 right: vec![
                     (
+                        unsafe { Span::new_from_raw_offset(10, 1, "-", ()) },
                         Operator::Subtract,
                         Expression::Literal(Literal::Character(literal::Character('c')))
                     ),
                     (
+                        unsafe { Span::new_from_raw_offset(4, 1, "+", ()) },
                         Operator::Add,
                         Expression::Literal(Literal::Character(literal::Character('b')))
                     ),
@@ -93,8 +106,83 @@ fn terms_reduce() {
                 operator: Operator::Multiply,
                 right: Box::new(Expression::Literal(Literal::Character(literal::Character(
                     'c'
-                ))))
-            }))
+                )))),
+                span: unsafe { Span::new_from_raw_offset(10, 1, "*", ()) },
+            })),
+            span: unsafe { Span::new_from_raw_offset(4, 1, "+", ()) },
         })
     );
 }
+
+#[test]
+fn terms_reduce_by_precedence() {
+    use super::binary::Binary;
+
+    let character = |character| {
+        Box::new(Expression::Literal(Literal::Character(literal::Character(
+            character,
+        ))))
+    };
+    let binary = |left, operator: Operator, offset, right| {
+        Box::new(Expression::Binary(Binary {
+            left,
+            span: unsafe { Span::new_from_raw_offset(offset, 1, operator.symbol(), ()) },
+            operator,
+            right,
+        }))
+    };
+
+    // Operators of equal precedence are left associative
+    assert_eq!(
+        Terms::parse("'a' * 'b' - 'c' * 'd' + 'e'".into())
+            .unwrap()
+            .1
+            .reduce(),
+        *binary(
+            binary(
+                binary(character('a'), Operator::Multiply, 4, character('b')),
+                Operator::Subtract,
+                10,
+                binary(character('c'), Operator::Multiply, 16, character('d')),
+            ),
+            Operator::Add,
+            22,
+            character('e'),
+        )
+    );
+
+    // Exponentiation is right associative
+    assert_eq!(
+        Terms::parse("'a' ** 'b' ** 'c'".into()).unwrap().1.reduce(),
+        *binary(
+            character('a'),
+            Operator::Exponent,
+            4,
+            binary(character('b'), Operator::Exponent, 11, character('c')),
+        )
+    );
+
+    // Arithmetic binds tighter than comparison, which binds tighter than logic
+    assert_eq!(
+        Terms::parse("'a' + 'b' == 'c' || 'd' < 'e' && 'f'".into())
+            .unwrap()
+            .1
+            .reduce(),
+        *binary(
+            binary(
+                binary(character('a'), Operator::Add, 4, character('b')),
+                Operator::Equal,
+                10,
+                character('c'),
+            ),
+            Operator::Or,
+            17,
+            binary(
+                binary(character('d'), Operator::LessThan, 24, character('e')),
+                Operator::And,
+                30,
+                character('f'),
+            ),
+        )
+    );
+}
@@ -5,9 +5,11 @@ use super::super::prelude::*;
 pub struct Unary<'a> {
     pub operator: Operator<'a>,
     pub expression: Box<Expression<'a>>,
+    /// The operator as it appears in the source
+    pub span: Span<'a>,
 }
 
-impl<'a> Unary<'a> {
+impl Unary<'_> {
     /// Parses a 'term' - an expression with N prefix and postfix operators
     pub fn parse(input: Span) -> IResult<Span, Expression> {
         let (input, term) = Term::parse(input)?;
@@ -21,42 +23,79 @@ pub enum Operator<'a> {
     Not,
     Access(Box<Expression<'a>>),
     Call(Vec<Expression<'a>>),
+    /// Conversion to another numeric type, for example `x as UInt8`
+    Convert(Type<'a>),
 }
 
 impl<'a> Operator<'a> {
-    pub fn parse_prefix(input: Span) -> IResult<Span, Self> {
-        let (input, result) =
-            alt((value(Self::Negate, tag("-")), value(Self::Not, tag("!"))))(input)?;
-        let (input, _) = whitespace::optional(input)?;
+    /// Parses a prefix operator, along with the span it was parsed from
+    pub fn parse_prefix(input: Span<'a>) -> IResult<Span<'a>, (Span<'a>, Self)> {
+        let (input, result) = consumed(alt((
+            value(Self::Negate, tag("-")),
+            value(Self::Not, tag("!")),
+        )))(input)?;
+        let (input, ()) = whitespace::optional(input)?;
         Ok((input, result))
     }
 
-    pub fn parse_postfix(input: Span<'a>) -> IResult<Span, Self> {
-        let (input, _) = whitespace::optional(input)?;
-        complete(alt((
+    /// Parses a postfix operator, along with the span it was parsed from
+    pub fn parse_postfix(input: Span<'a>) -> IResult<Span<'a>, (Span<'a>, Self)> {
+        let (input, ()) = whitespace::optional(input)?;
+        consumed(complete(alt((
             delimited(tag("["), Expression::parse, tag("]"))
                 .map(|expression| Self::Access(Box::new(expression))),
-            delimited(tag("("), many0(Expression::parse), tag(")")).map(Self::Call),
-        )))(input)
+            Self::parse_arguments.map(Self::Call),
+            preceded(pair(tag("as"), whitespace::required), Type::parse).map(Self::Convert),
+        ))))(input)
+    }
+
+    /// Parses the arguments of a call, which are separated by commas like a function's
+    /// parameters, with an optional trailing comma.
+    ///
+    /// Once the opening parenthesis has been parsed, anything else is a failure at the point where
+    /// the arguments stop. A missing comma fails with [`error::ErrorKind::Separator`] at the
+    /// argument that should have been preceded by one.
+    fn parse_arguments(input: Span<'a>) -> IResult<Span<'a>, Vec<Expression<'a>>> {
+        let (input, _) = tag("(")(input)?;
+        let (input, arguments) = separated_list0(tag(","), Expression::parse)(input)?;
+        let (input, ()) = if arguments.is_empty() {
+            whitespace::optional(input)?
+        } else {
+            opt(tag(","))
+                .and(whitespace::optional)
+                .map(|_| ())
+                .parse(input)?
+        };
+
+        if Expression::parse(input).is_ok() {
+            return Err(nom::Err::Failure(error::Error {
+                input,
+                kind: error::ErrorKind::Separator {
+                    between: "arguments",
+                },
+            }));
+        }
+        let (input, _) = cut(tag(")"))(input)?;
+        Ok((input, arguments))
     }
 
     pub const fn binding_power(&self) -> u8 {
         match self {
             Self::Negate | Self::Not => 10,
-            Self::Access(_) | Self::Call(_) => 20,
+            Self::Access(_) | Self::Call(_) | Self::Convert(_) => 20,
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Term<'a> {
-    prefix_operators: Vec<Operator<'a>>,
+    prefix_operators: Vec<(Span<'a>, Operator<'a>)>,
     expression: Expression<'a>,
-    postfix_operators: Vec<Operator<'a>>,
+    postfix_operators: Vec<(Span<'a>, Operator<'a>)>,
 }
 
 impl<'a> Term<'a> {
-    fn parse(input: Span<'a>) -> IResult<Span, Self> {
+    fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
         let (input, (prefix_operators, expression, postfix_operators)) = tuple((
             many0(Operator::parse_prefix),
             Expression::parse_atom,
@@ -86,28 +125,55 @@ impl<'a> Term<'a> {
             match (prefix_operators.pop(), postfix_operators.pop()) {
                 (None, None) => break,
                 (None, Some(operator)) | (Some(operator), None) => {
-                    expression = Expression::Unary(Unary {
-                        expression: Box::new(expression),
-                        operator,
-                    });
+                    expression = Self::apply(expression, operator);
                 }
                 (Some(prefix), Some(postfix)) => {
-                    expression = Expression::Unary(Unary {
-                        expression: Box::new(expression),
-                        operator: if prefix.binding_power() > postfix.binding_power() {
-                            postfix_operators.push(postfix);
-                            prefix
-                        } else {
-                            prefix_operators.push(prefix);
-                            postfix
-                        },
-                    });
+                    let operator = if prefix.1.binding_power() > postfix.1.binding_power() {
+                        postfix_operators.push(postfix);
+                        prefix
+                    } else {
+                        prefix_operators.push(prefix);
+                        postfix
+                    };
+                    expression = Self::apply(expression, operator);
                 }
             }
         }
 
         expression
     }
+
+    /// Applies an operator to an expression. A negated number literal is parsed as a negative
+    /// literal, so that it is range checked with its sign and the minimum of a type, such as
+    /// `-128i8`, can be written.
+    fn apply(
+        expression: Expression<'a>,
+        (span, operator): (Span<'a>, Operator<'a>),
+    ) -> Expression<'a> {
+        match (operator, expression) {
+            (Operator::Negate, Expression::Literal(Literal::Integer(integer)))
+                if integer.sign == number::Sign::Positive =>
+            {
+                Expression::Literal(Literal::Integer(literal::Integer {
+                    sign: number::Sign::Negative,
+                    ..integer
+                }))
+            }
+            (Operator::Negate, Expression::Literal(Literal::Float(float)))
+                if float.sign == number::Sign::Positive =>
+            {
+                Expression::Literal(Literal::Float(literal::Float {
+                    sign: number::Sign::Negative,
+                    ..float
+                }))
+            }
+            (operator, expression) => Expression::Unary(Unary {
+                expression: Box::new(expression),
+                operator,
+                span,
+            }),
+        }
+    }
 }
 
 #[test]
@@ -117,15 +183,19 @@ fn term_parses() {
         Ok((
             String::new(),
             Term {
-                prefix_operators: vec![Operator::Not],
+                prefix_operators: vec![(Span::new("!"), Operator::Not)],
                 expression: Expression::Literal(Literal::String(literal::String("a".to_string()))),
-                postfix_operators: vec![Operator::Access(Box::new(Expression::Literal(
-                    Literal::Integer(literal::Integer {
-                        base: number::Base::Decimal,
-                        digits: vec![1],
-                        sign: number::Sign::Positive
-                    })
-                )))]
+                postfix_operators: vec![(
+                    unsafe { Span::new_from_raw_offset(4, 1, "[1]", ()) },
+                    Operator::Access(Box::new(Expression::Literal(Literal::Integer(
+                        literal::Integer {
+                            base: number::Base::Decimal,
+                            digits: vec![1],
+                            sign: number::Sign::Positive,
+                            suffix: None
+                        }
+                    ))))
+                )]
             }
         ))
     );
@@ -145,14 +215,136 @@ fn term_reduces() {
                         literal::Integer {
                             base: number::Base::Decimal,
                             digits: vec![1],
-                            sign: number::Sign::Positive
+                            sign: number::Sign::Positive,
+                            suffix: None
                         }
                     )))),
                     expression: Box::new(Expression::Literal(Literal::String(literal::String(
                         "a".to_string()
-                    ))))
-                }))
+                    )))),
+                    span: unsafe { Span::new_from_raw_offset(4, 1, "[1]", ()) },
+                })),
+                span: Span::new("!"),
             })
         ))
     );
 }
+
+#[test]
+fn negative_literals_parse() {
+    let parse = |source| Unary::parse(source).unwrap().1;
+    let integer = |sign| {
+        Expression::Literal(Literal::Integer(literal::Integer {
+            base: number::Base::Decimal,
+            digits: vec![1, 2, 8],
+            sign,
+            suffix: Some(number::integer::Suffix::I8),
+        }))
+    };
+
+    assert_eq!(parse("-128i8".into()), integer(number::Sign::Negative));
+    assert!(matches!(
+        parse("-1.5".into()),
+        Expression::Literal(Literal::Float(literal::Float {
+            sign: number::Sign::Negative,
+            ..
+        }))
+    ));
+
+    // Only the innermost negation is part of the literal
+    let Expression::Unary(unary) = parse("--128i8".into()) else {
+        panic!("expected a negation");
+    };
+    assert_eq!(*unary.expression, integer(number::Sign::Negative));
+
+    // Postfix operators bind more tightly, so the literal is converted before it is negated
+    let Expression::Unary(unary) = parse("-128i8 as Int16".into()) else {
+        panic!("expected a negation");
+    };
+    assert!(matches!(*unary.expression, Expression::Unary(_)));
+}
+
+#[test]
+fn conversions_parse() {
+    assert_eq!(
+        test::strip_span(Operator::parse_postfix(" as UInt8 + 1".into())),
+        Ok((
+            " + 1".to_string(),
+            (
+                unsafe { Span::new_from_raw_offset(1, 1, "as UInt8", ()) },
+                Operator::Convert(Type::Named(Identifier(unsafe {
+                    Span::new_from_raw_offset(4, 1, "UInt8", ())
+                })))
+            )
+        ))
+    );
+    assert!(Operator::parse_postfix(" as_u8".into()).is_err());
+}
+
+#[test]
+fn calls_parse() {
+    /// Writes out the calls in an expression of identifiers and integers
+    fn calls(expression: &Expression) -> String {
+        match expression {
+            Expression::Identifier(identifier) => identifier.0.fragment().to_string(),
+            Expression::Literal(Literal::Integer(integer)) => {
+                integer.digits.iter().map(ToString::to_string).collect()
+            }
+            Expression::Unary(Unary {
+                operator: Operator::Call(arguments),
+                expression,
+                ..
+            }) => format!(
+                "{}({})",
+                calls(expression),
+                arguments.iter().map(calls).collect::<Vec<_>>().join(" ")
+            ),
+            _ => panic!("unexpected expression {expression:?}"),
+        }
+    }
+    let parse = |source| {
+        test::strip_span(Expression::parse(source)).map(|(input, expression)| {
+            assert!(input.is_empty(), "unparsed input: {input}");
+            calls(&expression)
+        })
+    };
+
+    assert_eq!(parse("f()".into()), Ok("f()".to_string()));
+    assert_eq!(parse("f( )".into()), Ok("f()".to_string()));
+    assert_eq!(parse("f(1)".into()), Ok("f(1)".to_string()));
+    assert_eq!(parse("f(1, 2)".into()), Ok("f(1 2)".to_string()));
+    assert_eq!(
+        parse("f(g(1), 2)(3)".into()),
+        Ok("f(g(1) 2)(3)".to_string())
+    );
+    assert_eq!(
+        parse("f(\n    long_argument,\n    g(h(), 2,),\n)".into()),
+        Ok("f(long_argument g(h() 2))".to_string())
+    );
+
+    // A missing comma fails where the comma should be
+    assert_eq!(
+        parse("f(1 2)".into()),
+        Err(nom::Err::Failure(error::Error {
+            input: "2)".to_string(),
+            kind: error::ErrorKind::Separator {
+                between: "arguments"
+            }
+        }))
+    );
+    assert_eq!(
+        parse("f(g(1) h(2))".into()),
+        Err(nom::Err::Failure(error::Error {
+            input: "h(2))".to_string(),
+            kind: error::ErrorKind::Separator {
+                between: "arguments"
+            }
+        }))
+    );
+    for source in ["f(,)", "f(1,,2)", "f(1"] {
+        assert!(
+            matches!(parse(source.into()), Err(nom::Err::Failure(_))),
+            "{source}"
+        );
+    }
+}
@@ -3,20 +3,26 @@ use super::prelude::*;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Function<'a> {
     pub parameters: Parameters<'a>,
-    pub return_type: Type<'a>,
+    /// The annotated return type, which is inferred if it is omitted
+    pub return_type: Option<Type<'a>>,
     pub body: Box<Expression<'a>>,
+    /// The `fn` keyword as it appears in the source
+    pub span: Span<'a>,
 }
 
 impl<'a> Function<'a> {
     pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
-        let (input, _) = delimited(whitespace::optional, tag("fn"), whitespace::optional)(input)?;
+        let (input, span) =
+            delimited(whitespace::optional, tag("fn"), whitespace::optional)(input)?;
         let (input, parameters) = delimited(
             pair(tag("("), whitespace::optional),
             Parameters::parse,
             pair(whitespace::optional, tag(")")),
         )(input)?;
-        let (input, _) = tuple((whitespace::optional, tag("->"), whitespace::optional))(input)?;
-        let (input, return_type) = Type::parse(input)?;
+        let (input, return_type) = opt(preceded(
+            tuple((whitespace::optional, tag("->"), whitespace::optional)),
+            Type::parse,
+        ))(input)?;
         let (input, body) = delimited(
             whitespace::optional,
             delimited(tag("{"), Expression::parse, tag("}")),
@@ -28,6 +34,7 @@ impl<'a> Function<'a> {
                 parameters,
                 return_type,
                 body: Box::new(body),
+                span,
             },
         ))
     }
@@ -44,50 +51,49 @@ fn function_parses() {
             Function {
                 parameters: Parameters(vec![
                     (
-                        Type(Identifier(unsafe {
+                        Some(Type::Named(Identifier(unsafe {
                             Span::new_from_raw_offset(3, 1, "Int64", ())
-                        })),
+                        }))),
                         Identifier(unsafe { Span::new_from_raw_offset(9, 1, "x", ()) })
                     ),
                     (
-                        Type(Identifier(unsafe {
+                        Some(Type::Named(Identifier(unsafe {
                             Span::new_from_raw_offset(12, 1, "Int64", ())
-                        })),
+                        }))),
                         Identifier(unsafe { Span::new_from_raw_offset(18, 1, "y", ()) })
                     )
                 ]),
-                return_type: Type(Identifier(unsafe {
+                return_type: Some(Type::Named(Identifier(unsafe {
                     Span::new_from_raw_offset(24, 1, "Int64", ())
-                })),
+                }))),
                 body: Box::new(Expression::Literal(Literal::Integer(literal::Integer {
                     base: number::Base::Decimal,
                     digits: vec![1],
-                    sign: number::Sign::Positive
-                })))
+                    sign: number::Sign::Positive,
+                    suffix: None
+                }))),
+                span: Span::new("fn"),
             }
         ))
     );
 }
 
+/// Parameters with an optional type annotation before each name, for example `Int64 x, y`
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Parameters<'a>(pub Vec<(Type<'a>, Identifier<'a>)>);
+pub struct Parameters<'a>(pub Vec<(Option<Type<'a>>, Identifier<'a>)>);
 
 impl<'a> Parameters<'a> {
     fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
-        let parse_seperator = |input| {
-            complete(delimited(
-                whitespace::optional,
-                tag(","),
-                whitespace::optional,
-            ))(input)
-        };
         terminated(
             separated_list0(
-                parse_seperator,
-                tuple((Type::parse, whitespace::required, Identifier::parse))
-                    .map(|(param_type, _, name)| (param_type, name)),
+                separator::parse,
+                alt((
+                    tuple((Type::parse, whitespace::required, Identifier::parse))
+                        .map(|(param_type, (), name)| (Some(param_type), name)),
+                    Identifier::parse.map(|name| (None, name)),
+                )),
             ),
-            opt(parse_seperator),
+            opt(separator::parse),
         )
         .map(Self)
         .parse(input)
@@ -102,16 +108,53 @@ fn parameters_parse() {
             String::new(),
             Parameters(vec![
                 (
-                    Type(Identifier(Span::new("Int64"))),
+                    Some(Type::Named(Identifier(Span::new("Int64")))),
                     Identifier(unsafe { Span::new_from_raw_offset(6, 1, "x", ()) })
                 ),
                 (
-                    Type(Identifier(unsafe {
+                    Some(Type::Named(Identifier(unsafe {
                         Span::new_from_raw_offset(9, 1, "Int64", ())
-                    })),
+                    }))),
                     Identifier(unsafe { Span::new_from_raw_offset(15, 1, "y", ()) })
                 )
             ])
         ))
     );
 }
+
+#[test]
+fn unannotated_parameters_parse() {
+    assert_eq!(
+        test::strip_span(Parameters::parse("Int64 x, y".into())),
+        Ok((
+            String::new(),
+            Parameters(vec![
+                (
+                    Some(Type::Named(Identifier(Span::new("Int64")))),
+                    Identifier(unsafe { Span::new_from_raw_offset(6, 1, "x", ()) })
+                ),
+                (
+                    None,
+                    Identifier(unsafe { Span::new_from_raw_offset(9, 1, "y", ()) })
+                )
+            ])
+        ))
+    );
+    assert_eq!(
+        test::strip_span(Function::parse("fn(x) { x }".into())),
+        Ok((
+            String::new(),
+            Function {
+                parameters: Parameters(vec![(
+                    None,
+                    Identifier(unsafe { Span::new_from_raw_offset(3, 1, "x", ()) })
+                )]),
+                return_type: None,
+                body: Box::new(Expression::Identifier(Identifier(unsafe {
+                    Span::new_from_raw_offset(8, 1, "x", ())
+                }))),
+                span: Span::new("fn"),
+            }
+        ))
+    );
+}
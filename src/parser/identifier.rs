@@ -4,7 +4,7 @@ use super::prelude::*;
 pub struct Identifier<'a>(pub Span<'a>);
 
 impl<'a> Identifier<'a> {
-    pub fn parse(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
         let (input, (identifier, _)) = consumed(pair(
             satisfy(|character: char| character.is_alphabetic() || character == '_'),
             take_while(|character: char| character.is_alphanumeric() || character == '_'),
@@ -12,10 +12,10 @@ impl<'a> Identifier<'a> {
         .parse(input)?;
 
         match identifier.to_string().as_str() {
-            "in" => Err(nom::Err::Error(nom::error::Error {
-                input: identifier,
-                code: nom::error::ErrorKind::Fail,
-            })),
+            "in" | "if" | "then" | "else" => Err(nom::Err::Error(error::Error::new(
+                identifier,
+                nom::error::ErrorKind::Fail,
+            ))),
             _ => Ok((input, Self(identifier))),
         }
     }
@@ -39,4 +39,9 @@ fn identifiers_parse() {
     );
     assert!(test::strip_span(Identifier::parse(";".into())).is_err());
     assert!(test::strip_span(Identifier::parse("in".into())).is_err());
+    assert!(test::strip_span(Identifier::parse("else".into())).is_err());
+    assert_eq!(
+        test::strip_span(Identifier::parse("iffy".into())),
+        Ok((String::new(), Identifier(Span::new("iffy"))))
+    );
 }
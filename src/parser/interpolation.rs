@@ -0,0 +1,128 @@
+use super::prelude::*;
+use literal::string::Content;
+
+/// A string with expressions embedded in braces, for example
+///
+/// ```!
+/// "hello {name}, you are {age + 1}"
+/// ```
+///
+/// Each expression is converted to text when the string is evaluated. `\{` and `\}` are
+/// literal braces, and raw strings are never interpolated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpolation<'a> {
+    pub segments: Vec<Segment<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(String),
+    Expression {
+        expression: Expression<'a>,
+        /// The embedded expression as it appears in the source
+        span: Span<'a>,
+    },
+}
+
+impl<'a> Interpolation<'a> {
+    /// Parses a quoted or block string with at least one embedded expression, leaving strings
+    /// without any to be parsed as literals
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
+        let (rest, contents) = literal::String::parse_contents(input, Segment::parse_embedded)?;
+
+        let mut segments = Vec::new();
+        for content in contents {
+            match content {
+                Content::Character { character, .. } => {
+                    if let Some(Segment::Text(text)) = segments.last_mut() {
+                        text.push(character);
+                    } else {
+                        segments.push(Segment::Text(character.into()));
+                    }
+                }
+                Content::Embedded((span, expression)) => {
+                    segments.push(Segment::Expression { expression, span });
+                }
+            }
+        }
+
+        if segments
+            .iter()
+            .all(|segment| matches!(segment, Segment::Text(_)))
+        {
+            return Err(nom::Err::Error(error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )));
+        }
+        Ok((rest, Self { segments }))
+    }
+}
+
+impl<'a> Segment<'a> {
+    /// Parses an expression in braces, along with the span of the expression. Once a brace is
+    /// found, it has to be followed by an expression and a closing brace.
+    fn parse_embedded(input: Span<'a>) -> IResult<Span<'a>, (Span<'a>, Expression<'a>)> {
+        let (input, _) = tag("{")(input)?;
+        let (input, embedded) =
+            cut(preceded(whitespace::optional, consumed(Expression::parse)))(input)?;
+        let (input, _) = cut(tag("}"))(input)?;
+        Ok((input, embedded))
+    }
+}
+
+#[test]
+fn interpolation_parses() {
+    let parse = |source| {
+        let (input, interpolation) = Interpolation::parse(source).unwrap();
+        assert!(input.is_empty(), "unparsed input: {input}");
+        interpolation
+            .segments
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text,
+                Segment::Expression { span, .. } => format!(
+                    "[{}:{} {}]",
+                    span.location_line(),
+                    span.get_utf8_column(),
+                    span.fragment()
+                ),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        parse("\"hello {name}, you are {age + 1}\"".into()),
+        ["hello ", "[1:9 name]", ", you are ", "[1:25 age + 1]"]
+    );
+    assert_eq!(
+        parse("\"{ f(\"{x}\") }\\{}\"".into()),
+        ["[1:4 f(\"{x}\") ]", "{}"]
+    );
+    assert_eq!(
+        parse("\"\"\"\n    total:\n      {a\n        + b}\n    \"\"\"".into()),
+        ["total:\n  ", "[3:8 a\n        + b]"]
+    );
+}
+
+#[test]
+fn interpolation_errors() {
+    let parse = |source| test::strip_span(Interpolation::parse(source));
+
+    // Strings without embedded expressions are literals
+    assert_eq!(
+        parse("\"no \\{braces\\}\"".into()),
+        Err(nom::Err::Error(error::Error::new(
+            "\"no \\{braces\\}\"".to_string(),
+            nom::error::ErrorKind::Verify
+        )))
+    );
+    assert!(parse("r\"{raw}\"".into()).is_err());
+
+    assert!(matches!(parse("\"{}\"".into()), Err(nom::Err::Failure(_))));
+    assert!(matches!(
+        parse("\"{1 + }\"".into()),
+        Err(nom::Err::Failure(_))
+    ));
+    assert!(matches!(parse("\"{x\"".into()), Err(nom::Err::Failure(_))));
+}
@@ -4,26 +4,55 @@ use super::prelude::*;
 ///
 /// ```!
 /// let
-///     x = 2
+///     x = 2,
+///     y = -x,
 /// in
-///     x ^ x
+///     x ^ y
+/// ```
+///
+/// Assignments are separated by commas, with an optional trailing comma:
+///
+/// ```!
+/// let-in      = "let" ["rec"] assignments "in" expression
+/// assignments = assignment { "," assignment } [","]
+/// assignment  = identifier "=" expression
+/// ```
+///
+/// With `let rec`, every assignment can refer to every other, for example
+///
+/// ```!
+/// let rec
+///     even = fn(Int64 n) -> Boolean { n == 0 || odd(n - 1) },
+///     odd = fn(Int64 n) -> Boolean { n != 0 && even(n - 1) },
+/// in
+///     even(10)
 /// ```
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LetIn<'a> {
+    pub recursive: bool,
     pub assignments: Vec<Assignment<'a>>,
     pub expression: Box<Expression<'a>>,
 }
 
 impl<'a> LetIn<'a> {
-    pub fn parse(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
         let (input, _) = delimited(whitespace::optional, tag("let"), whitespace::optional)(input)?;
-        let (input, assignments) = many1(Assignment::parse)(input)?;
+        // `rec` is only a keyword when it is not itself being assigned to
+        let (input, recursive) = opt(tuple((
+            tag("rec"),
+            whitespace::required,
+            not(tag("=")),
+        )))
+        .map(|recursive| recursive.is_some())
+        .parse(input)?;
+        let (input, assignments) = Assignment::parse_list(input)?;
         let (input, _) = delimited(whitespace::optional, tag("in"), whitespace::optional)(input)?;
         let (input, expression) = Expression::parse(input)?;
 
         Ok((
             input,
             Self {
+                recursive,
                 assignments,
                 expression: Box::new(expression),
             },
@@ -38,6 +67,7 @@ fn let_in_parses() {
         Ok((
             String::new(),
             LetIn {
+                recursive: false,
                 assignments: vec![Assignment {
                     identifier: Identifier(unsafe {
                         Span::new_from_raw_offset(4, 1, "a_useless_value", ())
@@ -45,19 +75,33 @@ fn let_in_parses() {
                     value: Expression::Literal(Literal::Integer(literal::Integer {
                         base: number::Base::Decimal,
                         digits: vec![1],
-                        sign: number::Sign::Positive
+                        sign: number::Sign::Positive,
+                        suffix: None
                     }))
                 }],
                 expression: Box::new(Expression::Literal(Literal::Integer(literal::Integer {
                     base: number::Base::Decimal,
                     digits: vec![1],
-                    sign: number::Sign::Positive
+                    sign: number::Sign::Positive,
+                    suffix: None
                 })))
             }
         ))
     );
 }
 
+#[test]
+fn let_rec_parses() {
+    let parse = |source| LetIn::parse(source).map(|(_, let_in)| let_in);
+
+    assert!(parse("let rec a = 1 in a".into()).unwrap().recursive);
+    assert!(!parse("let record = 1 in record".into()).unwrap().recursive);
+
+    let let_in = parse("let rec = 1 in rec".into()).unwrap();
+    assert!(!let_in.recursive);
+    assert_eq!(*let_in.assignments[0].identifier.0.fragment(), "rec");
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Assignment<'a> {
     pub identifier: Identifier<'a>,
@@ -65,13 +109,33 @@ pub struct Assignment<'a> {
 }
 
 impl<'a> Assignment<'a> {
-    pub fn parse(input: Span<'a>) -> IResult<Span, Self> {
+    pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
         let (input, identifier) = Identifier::parse(input)?;
         let (input, _) = delimited(whitespace::optional, tag("="), whitespace::optional)(input)?;
         let (input, value) = Expression::parse(input)?;
 
         Ok((input, Self { identifier, value }))
     }
+
+    /// Parses assignments separated by commas, with an optional trailing comma. An assignment
+    /// that follows another without a comma fails with [`error::ErrorKind::Separator`] where the
+    /// comma is missing.
+    fn parse_list(input: Span<'a>) -> IResult<Span<'a>, Vec<Self>> {
+        let (input, assignments) = terminated(
+            separated_list1(separator::parse, Self::parse),
+            opt(separator::parse),
+        )(input)?;
+
+        if Self::parse(input).is_ok() {
+            return Err(nom::Err::Failure(error::Error {
+                input,
+                kind: error::ErrorKind::Separator {
+                    between: "assignments",
+                },
+            }));
+        }
+        Ok((input, assignments))
+    }
 }
 
 #[test]
@@ -85,9 +149,61 @@ fn assignment_parses() {
                 value: Expression::Literal(Literal::Integer(literal::Integer {
                     base: number::Base::Decimal,
                     digits: vec![1],
-                    sign: number::Sign::Positive
+                    sign: number::Sign::Positive,
+                    suffix: None
                 }))
             }
         ))
     );
 }
+
+#[test]
+fn assignments_are_separated() {
+    let parse = |source| {
+        test::strip_span(LetIn::parse(source)).map(|(input, let_in)| {
+            let names: Vec<_> = let_in
+                .assignments
+                .iter()
+                .map(|assignment| assignment.identifier.0.fragment().to_string())
+                .collect();
+            (input, names, let_in.assignments)
+        })
+    };
+
+    let (input, names, assignments) = parse("let a = 1, b = -2 in a + b".into()).unwrap();
+    assert_eq!((input.as_str(), names), ("", vec!["a".into(), "b".into()]));
+    assert!(matches!(assignments[1].value, Expression::Literal(_)));
+
+    // A subtraction is only parsed within an assignment
+    let (_, names, assignments) = parse("let a = 1 - 2, b = - a in b".into()).unwrap();
+    assert_eq!(names, ["a", "b"]);
+    assert!(matches!(assignments[0].value, Expression::Binary(_)));
+
+    // Line breaks do not separate assignments, so a call can continue onto the next line
+    let (_, names, assignments) =
+        parse("let\n    a = f\n        (1),\n    b = g(2)(3),\nin a".into()).unwrap();
+    assert_eq!(names, ["a", "b"]);
+    assert!(matches!(assignments[0].value, Expression::Unary(_)));
+
+    // A missing comma fails at the assignment that should have been preceded by one
+    assert_eq!(
+        parse("let a = 1 b = -2 in a".into()).map(|(input, ..)| input),
+        Err(nom::Err::Failure(error::Error {
+            input: "b = -2 in a".to_string(),
+            kind: error::ErrorKind::Separator {
+                between: "assignments"
+            }
+        }))
+    );
+    assert_eq!(
+        parse("let a = f\n  b = (1) in a".into()).map(|(input, ..)| input),
+        Err(nom::Err::Failure(error::Error {
+            input: "b = (1) in a".to_string(),
+            kind: error::ErrorKind::Separator {
+                between: "assignments"
+            }
+        }))
+    );
+    assert!(parse("let , a = 1 in a".into()).is_err());
+    assert!(parse("let a = 1,, b = 2 in a".into()).is_err());
+}
@@ -39,7 +39,8 @@ fn literal_parses() {
             Literal::Integer(Integer {
                 base: number::Base::Decimal,
                 digits: vec![1, 0],
-                sign: number::Sign::Positive
+                sign: number::Sign::Positive,
+                suffix: None
             })
         ))
     );
@@ -53,7 +54,8 @@ fn literal_parses() {
                 whole: vec![1, 0],
                 fractional: vec![0],
                 sign: number::Sign::Positive,
-                exponent: None
+                exponent: None,
+                suffix: None
             })
         ))
     );
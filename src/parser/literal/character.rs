@@ -11,21 +11,94 @@ impl Character {
         Ok((input, Self(character)))
     }
 
+    /// Parses a single character, which may be written as an escape sequence starting with a
+    /// backslash.
+    ///
+    /// An invalid escape sequence fails with [`error::ErrorKind::Escape`] at its backslash, as a
+    /// failure rather than an error so that no other parser is tried in its place.
     pub fn parse_char(input: Span) -> IResult<Span, char> {
-        let (input, result) = satisfy(|character| character != '\\')
-            .or(tag("\\")
-                .and(|input| {
-                    alt((
-                        value('\n', tag("n")),
-                        value('\r', tag("r")),
-                        value('\'', tag("'")),
-                        value('\"', tag("\"")),
-                        value('\0', tag("0")),
-                    ))(input)
-                })
-                .map(|result| result.1))
-            .parse(input)?;
-        Ok((input, result))
+        if !input.fragment().starts_with('\\') {
+            return satisfy(|character| character != '\\')(input);
+        }
+
+        preceded(
+            tag("\\"),
+            alt((
+                value('\n', tag("n")),
+                value('\r', tag("r")),
+                value('\t', tag("t")),
+                value('\\', tag("\\")),
+                value('\'', tag("'")),
+                value('\"', tag("\"")),
+                value('{', tag("{")),
+                value('}', tag("}")),
+                value('\0', tag("0")),
+                Self::parse_byte,
+                Self::parse_unicode,
+            )),
+        )(input)
+        .map_err(|_| {
+            nom::Err::Failure(error::Error {
+                input,
+                kind: error::ErrorKind::Escape {
+                    sequence: Self::invalid_escape(input),
+                },
+            })
+        })
+    }
+
+    /// The text of the invalid escape sequence at the start of the input, which is the backslash
+    /// and the character after it along with any digits of a byte or Unicode escape
+    fn invalid_escape(input: Span) -> String {
+        let hex = |digit: char| digit.is_ascii_hexdigit();
+        let sequence: IResult<Span, Span> = nom::combinator::recognize(preceded(
+            tag("\\"),
+            alt((
+                value((), preceded(tag("x"), take_while_m_n(0, 2, hex))),
+                value(
+                    (),
+                    preceded(
+                        tag("u"),
+                        opt(tuple((tag("{"), take_while(hex), opt(tag("}"))))),
+                    ),
+                ),
+                value((), opt(satisfy(|_| true))),
+            )),
+        ))(input);
+        sequence.map_or_else(|_| input.to_string(), |(_, sequence)| sequence.to_string())
+    }
+
+    /// Parses the `x7F` of a byte escape, which is two hexadecimal digits of an ASCII character
+    fn parse_byte(input: Span) -> IResult<Span, char> {
+        map_opt(
+            preceded(
+                tag("x"),
+                take_while_m_n(2, 2, |digit: char| digit.is_ascii_hexdigit()),
+            ),
+            |digits: Span| {
+                u8::from_str_radix(digits.fragment(), 16)
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .map(char::from)
+            },
+        )(input)
+    }
+
+    /// Parses the `u{1F600}` of a Unicode escape, which is up to six hexadecimal digits of a
+    /// Unicode scalar value
+    fn parse_unicode(input: Span) -> IResult<Span, char> {
+        map_opt(
+            delimited(
+                tag("u{"),
+                take_while_m_n(1, 6, |digit: char| digit.is_ascii_hexdigit()),
+                tag("}"),
+            ),
+            |digits: Span| {
+                u32::from_str_radix(digits.fragment(), 16)
+                    .ok()
+                    .and_then(char::from_u32)
+            },
+        )(input)
     }
 }
 
@@ -42,3 +115,52 @@ fn character_parses() {
         Ok((String::new(), Character('\n')))
     );
 }
+
+#[test]
+fn escapes_parse() {
+    use super::super::test;
+
+    for (source, character) in [
+        ("'\\n'", '\n'),
+        ("'\\t'", '\t'),
+        ("'\\\\'", '\\'),
+        ("'\\''", '\''),
+        ("'\\0'", '\0'),
+        ("'\\{'", '{'),
+        ("'\\x41'", 'A'),
+        ("'\\x7f'", '\x7f'),
+        ("'\\u{e9}'", 'é'),
+        ("'\\u{1F600}'", '😀'),
+        ("'\\u{10FFFF}'", '\u{10ffff}'),
+    ] {
+        assert_eq!(
+            test::strip_span(Character::parse(source.into())),
+            Ok((String::new(), Character(character))),
+            "{source}"
+        );
+    }
+
+    // Invalid escapes fail at their backslash with the offending sequence
+    for (source, sequence) in [
+        ("'\\q'", "\\q"),
+        ("'\\x80'", "\\x80"),
+        ("'\\x4'", "\\x4"),
+        ("'\\u{}'", "\\u{}"),
+        ("'\\u{D800}'", "\\u{D800}"),
+        ("'\\u{110000}'", "\\u{110000}"),
+        ("'\\u{1234567}'", "\\u{1234567}"),
+        ("'\\u41'", "\\u"),
+        ("'\\", "\\"),
+    ] {
+        assert_eq!(
+            test::strip_span(Character::parse(source.into())),
+            Err(nom::Err::Failure(error::Error {
+                input: source[1..].to_string(),
+                kind: error::ErrorKind::Escape {
+                    sequence: sequence.to_string()
+                }
+            })),
+            "{source}"
+        );
+    }
+}
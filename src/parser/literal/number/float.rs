@@ -7,6 +7,7 @@ pub struct Float {
     pub fractional: Vec<u8>,
     pub sign: Sign,
     pub exponent: Option<Exponent>,
+    pub suffix: Option<Suffix>,
 }
 
 impl Float {
@@ -20,10 +21,29 @@ impl Float {
         let (input, _) = tag(".")(input)?;
         let (input, fractional) = base.parse_digits(input)?;
 
-        let (input, exponent) = if base == Base::Decimal {
-            Exponent::parse(input)?
+        // An `e` exponent or a suffix would be ambiguous with hexadecimal digits, so other bases
+        // have a `p` exponent instead, after which a suffix is unambiguous
+        let (input, (exponent, suffix)) = if base == Base::Decimal {
+            let (rest, (exponent, suffix)) = Exponent::parse.and(opt(Suffix::parse)).parse(input)?;
+
+            // A fractional exponent generally makes the value irrational, so it cannot be exact
+            if suffix == Some(Suffix::Rational)
+                && exponent.as_ref().is_some_and(Exponent::is_fractional)
+            {
+                return Err(nom::Err::Failure(error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Verify,
+                )));
+            }
+            (rest, (exponent, suffix))
         } else {
-            (input, None)
+            match Exponent::parse_binary(input)? {
+                (input, Some(exponent)) => {
+                    let (input, suffix) = opt(Suffix::parse)(input)?;
+                    (input, (Some(exponent), suffix))
+                }
+                (input, None) => (input, (None, None)),
+            }
         };
 
         Ok((
@@ -34,11 +54,31 @@ impl Float {
                 fractional,
                 sign,
                 exponent,
+                suffix,
             },
         ))
     }
 }
 
+/// A suffix giving a float literal a type other than `Float64`, for example the `f32` in `1.5f32`,
+/// or the `r` in `0.1r` for an exact `Rational`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suffix {
+    F32,
+    F64,
+    Rational,
+}
+
+impl Suffix {
+    pub fn parse(input: Span) -> IResult<Span, Self> {
+        alt((
+            value(Self::F32, tag("f32")),
+            value(Self::F64, tag("f64")),
+            value(Self::Rational, tag("r")),
+        ))(input)
+    }
+}
+
 #[test]
 fn float_parses() {
     assert_eq!(
@@ -50,7 +90,8 @@ fn float_parses() {
                 whole: Vec::new(),
                 fractional: vec![5],
                 sign: Sign::Positive,
-                exponent: None
+                exponent: None,
+                suffix: None
             }
         ))
     );
@@ -63,7 +104,8 @@ fn float_parses() {
                 whole: vec![4],
                 fractional: vec![2],
                 sign: Sign::Positive,
-                exponent: None
+                exponent: None,
+                suffix: None
             }
         ))
     );
@@ -76,12 +118,111 @@ fn float_parses() {
                 whole: vec![0xf, 0xf],
                 fractional: vec![0],
                 sign: Sign::Positive,
-                exponent: None
+                exponent: None,
+                suffix: None
+            }
+        ))
+    );
+}
+
+#[test]
+fn float_suffixes_parse() {
+    assert_eq!(
+        test::strip_span(Float::parse("1.5e3f32".into())),
+        Ok((
+            String::new(),
+            Float {
+                base: Base::Decimal,
+                whole: vec![1],
+                fractional: vec![5],
+                sign: Sign::Positive,
+                exponent: Some(Exponent {
+                    whole: vec![3],
+                    fractional: Vec::new(),
+                    sign: Sign::Positive
+                }),
+                suffix: Some(Suffix::F32),
+            }
+        ))
+    );
+    // A hexadecimal `f` is a digit, not a suffix
+    assert_eq!(
+        test::strip_span(Float::parse("0x1.f32".into())).map(|(input, float)| (
+            input,
+            float.fractional,
+            float.suffix
+        )),
+        Ok((String::new(), vec![0xf, 3, 2], None))
+    );
+    assert_eq!(
+        test::strip_span(Float::parse("0.1r".into())).map(|(input, float)| (input, float.suffix)),
+        Ok((String::new(), Some(Suffix::Rational)))
+    );
+    // Exact literals can only have whole exponents
+    assert_eq!(
+        test::strip_span(Float::parse("1.5e2.0r".into())).map(|(input, _)| input),
+        Ok(String::new())
+    );
+    assert_eq!(
+        test::strip_span(Float::parse("1.0e0.5r".into())),
+        Err(nom::Err::Failure(error::Error::new(
+            "e0.5r".to_string(),
+            nom::error::ErrorKind::Verify
+        )))
+    );
+}
+
+#[test]
+fn binary_exponents_parse() {
+    assert_eq!(
+        test::strip_span(Float::parse("0x1.8p3".into())),
+        Ok((
+            String::new(),
+            Float {
+                base: Base::Hexadecimal,
+                whole: vec![1],
+                fractional: vec![8],
+                sign: Sign::Positive,
+                exponent: Some(Exponent {
+                    whole: vec![3],
+                    fractional: Vec::new(),
+                    sign: Sign::Positive
+                }),
+                suffix: None,
             }
         ))
     );
+    assert_eq!(
+        test::strip_span(Float::parse("-0b1.1P-10f32".into())),
+        Ok((
+            String::new(),
+            Float {
+                base: Base::Binary,
+                whole: vec![1],
+                fractional: vec![1],
+                sign: Sign::Negative,
+                exponent: Some(Exponent {
+                    whole: vec![1, 0],
+                    fractional: Vec::new(),
+                    sign: Sign::Negative
+                }),
+                suffix: Some(Suffix::F32),
+            }
+        ))
+    );
+    // Binary exponents have no fractional part, and decimal floats have no binary exponent
+    assert_eq!(
+        test::strip_span(Float::parse("0x1.0p1.5".into())).map(|(input, _)| input),
+        Ok(".5".to_string())
+    );
+    assert_eq!(
+        test::strip_span(Float::parse("1.0p1".into())).map(|(input, _)| input),
+        Ok("p1".to_string())
+    );
 }
 
+/// The exponent of a float literal. Decimal floats are scaled by a power of ten, as in `1.5e3`,
+/// and floats in other bases by a power of two, as in `0x1.8p3`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Exponent {
     pub whole: Vec<u8>,
@@ -111,6 +252,30 @@ impl Exponent {
             }),
         ))
     }
+
+    /// Whether the exponent has a fractional part other than zero
+    pub fn is_fractional(&self) -> bool {
+        self.fractional.iter().any(|&digit| digit != 0)
+    }
+
+    /// Parses a `p` exponent, which is a whole number of decimal digits
+    pub fn parse_binary(input: Span) -> IResult<Span, Option<Self>> {
+        let (input, Some(_)) = opt(alt((tag("p"), tag("P"))))(input)? else {
+            return Ok((input, None))
+        };
+
+        let (input, sign) = Sign::parse(input)?;
+        let (input, whole) = Base::Decimal.parse_digits(input)?;
+
+        Ok((
+            input,
+            Some(Self {
+                whole,
+                fractional: Vec::new(),
+                sign,
+            }),
+        ))
+    }
 }
 
 #[test]
@@ -5,6 +5,7 @@ pub struct Integer {
     pub base: Base,
     pub digits: Vec<u8>,
     pub sign: Sign,
+    pub suffix: Option<Suffix>,
 }
 
 impl Integer {
@@ -12,8 +13,50 @@ impl Integer {
         let (input, sign) = Sign::parse(input)?;
         let (input, base) = Base::parse(input)?;
         let (input, digits) = base.parse_digits(input)?;
+        let (input, suffix) = opt(Suffix::parse)(input)?;
 
-        Ok((input, Self { base, digits, sign }))
+        Ok((
+            input,
+            Self {
+                base,
+                digits,
+                sign,
+                suffix,
+            },
+        ))
+    }
+}
+
+/// A suffix giving an integer literal a type other than `Int64`, for example the `u8` in `255u8`,
+/// the `n` in `1n` for a `BigInt`, or the `r` in `1r` for a `Rational`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suffix {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Big,
+    Rational,
+}
+
+impl Suffix {
+    pub fn parse(input: Span) -> IResult<Span, Self> {
+        alt((
+            value(Self::I8, tag("i8")),
+            value(Self::I16, tag("i16")),
+            value(Self::I32, tag("i32")),
+            value(Self::I64, tag("i64")),
+            value(Self::U8, tag("u8")),
+            value(Self::U16, tag("u16")),
+            value(Self::U32, tag("u32")),
+            value(Self::U64, tag("u64")),
+            value(Self::Big, tag("n")),
+            value(Self::Rational, tag("r")),
+        ))(input)
     }
 }
 
@@ -27,6 +70,7 @@ fn integer_parses() {
                 base: Base::Decimal,
                 digits: vec![4, 2],
                 sign: Sign::Positive,
+                suffix: None,
             }
         ))
     );
@@ -38,7 +82,53 @@ fn integer_parses() {
                 base: Base::Hexadecimal,
                 digits: vec![0xf, 0xf],
                 sign: Sign::Positive,
+                suffix: None,
+            }
+        ))
+    );
+}
+
+#[test]
+fn integer_suffixes_parse() {
+    assert_eq!(
+        test::strip_span(Integer::parse("0xffu8".into())),
+        Ok((
+            String::new(),
+            Integer {
+                base: Base::Hexadecimal,
+                digits: vec![0xf, 0xf],
+                sign: Sign::Positive,
+                suffix: Some(Suffix::U8),
+            }
+        ))
+    );
+    assert_eq!(
+        test::strip_span(Integer::parse("-1_i32 ".into())),
+        Ok((
+            " ".to_string(),
+            Integer {
+                base: Base::Decimal,
+                digits: vec![1],
+                sign: Sign::Negative,
+                suffix: Some(Suffix::I32),
             }
         ))
     );
+    assert_eq!(
+        test::strip_span(Integer::parse("0b11n".into())),
+        Ok((
+            String::new(),
+            Integer {
+                base: Base::Binary,
+                digits: vec![1, 1],
+                sign: Sign::Positive,
+                suffix: Some(Suffix::Big),
+            }
+        ))
+    );
+    assert_eq!(
+        test::strip_span(Integer::parse("7r".into()))
+            .map(|(input, integer)| (input, integer.suffix)),
+        Ok((String::new(), Some(Suffix::Rational)))
+    );
 }
@@ -1,3 +1,5 @@
+use std::convert::Infallible;
+
 use super::super::prelude::*;
 
 use super::Character;
@@ -6,17 +8,145 @@ use super::Character;
 pub struct String(pub std::string::String);
 
 impl String {
+    /// Parses a string in any of its forms, which all give the same kind of literal
     pub fn parse(input: Span) -> IResult<Span, Self> {
+        alt((Self::parse_raw, |input| {
+            let (input, contents) =
+                Self::parse_contents(input, nom::combinator::fail::<_, Infallible, _>)?;
+            let string = contents
+                .into_iter()
+                .map(|content| match content {
+                    Content::Character { character, .. } => character,
+                    Content::Embedded(never) => match never {},
+                })
+                .collect();
+            Ok((input, Self(string)))
+        }))(input)
+    }
+
+    /// Parses a quoted or block string, trying `embedded` before each character so that other
+    /// syntax can be embedded in the string.
+    ///
+    /// A block string starts with `"""` and a line break and ends with `"""`. The line breaks
+    /// after the opening quotes and before closing quotes on a line of their own are not part
+    /// of the string, and neither is the indentation common to every line, so a block string
+    /// can be indented along with the code around it.
+    pub fn parse_contents<'a, T>(
+        input: Span<'a>,
+        mut embedded: impl FnMut(Span<'a>) -> IResult<Span<'a>, T>,
+    ) -> IResult<Span<'a>, Vec<Content<T>>> {
+        let content = |input: Span<'a>| match embedded(input) {
+            Ok((input, embedded)) => Ok((input, Content::Embedded(embedded))),
+            Err(nom::Err::Error(_)) => consumed(Character::parse_char)
+                .map(|(source, character)| Content::Character {
+                    character,
+                    escaped: source.fragment().starts_with('\\'),
+                })
+                .parse(input),
+            Err(error) => Err(error),
+        };
+
+        if let (input, Some(_)) = opt(tag("\"\"\""))(input)? {
+            let (input, _) = cut(pair(
+                take_while(|character| character == ' ' || character == '\t'),
+                line_ending,
+            ))(input)?;
+            let (input, contents) = many0(preceded(not(tag("\"\"\"")), content))(input)?;
+            let (input, _) = cut(tag("\"\"\""))(input)?;
+            return Ok((input, strip_indentation(contents)));
+        }
+
+        delimited(
+            tag("\""),
+            many0(preceded(not(tag("\"")), content)),
+            tag("\""),
+        )(input)
+    }
+
+    /// Parses a raw string, such as `r"C:\temp"`, in which backslashes are not escapes. Any
+    /// number of `#`s may surround the quotes, as in `r#"say "hi""#`, so that the string can
+    /// contain a quote followed by fewer `#`s.
+    fn parse_raw(input: Span) -> IResult<Span, Self> {
+        let (input, _) = tag("r")(input)?;
+        let (input, hashes) = take_while(|character| character == '#')(input)?;
         let (input, _) = tag("\"")(input)?;
-        let (input, chars) = many0(
-            not(tag("\""))
-                .and(Character::parse_char)
-                .map(|(_, result)| result),
-        )(input)?;
-        let (input, _) = tag("\"")(input)?;
-        dbg!(&chars);
-        Ok((input, Self(chars.iter().collect())))
+
+        let closing = format!("\"{hashes}");
+        let (input, contents) = cut(take_until(closing.as_str()))(input)?;
+        let (input, _) = tag(closing.as_str())(input)?;
+        Ok((input, Self(contents.fragment().to_string())))
+    }
+}
+
+/// A character of a quoted or block string, or something embedded in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content<T> {
+    Character { character: char, escaped: bool },
+    Embedded(T),
+}
+
+impl<T> Content<T> {
+    const fn is_unescaped(&self, expected: char) -> bool {
+        matches!(self, Self::Character { character, escaped: false } if *character == expected)
+    }
+
+    const fn is_space(&self) -> bool {
+        self.is_unescaped(' ') || self.is_unescaped('\t')
+    }
+}
+
+/// Removes the indentation common to every line of a block string. Escaped spaces and line
+/// breaks are kept as they are, so they can be used to write indentation or line breaks that
+/// are not stripped.
+fn strip_indentation<T>(contents: Vec<Content<T>>) -> Vec<Content<T>> {
+    let mut lines = Vec::new();
+    let mut line = Vec::new();
+    for content in contents {
+        if content.is_unescaped('\n') {
+            lines.push(std::mem::take(&mut line));
+        } else {
+            line.push(content);
+        }
+    }
+    lines.push(line);
+    for line in &mut lines {
+        if line
+            .last()
+            .is_some_and(|content| content.is_unescaped('\r'))
+        {
+            line.pop();
+        }
+    }
+
+    // The indentation of closing quotes on a line of their own counts as well, so that it can
+    // be used to keep some of the indentation of the other lines
+    let closing = lines
+        .last()
+        .filter(|line| line.iter().all(Content::is_space))
+        .map(Vec::len);
+    if closing.is_some() {
+        lines.pop();
+    }
+
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.iter().all(Content::is_space))
+        .map(|line| line.iter().take_while(|content| content.is_space()).count())
+        .chain(closing)
+        .min()
+        .unwrap_or_default();
+
+    let mut contents = Vec::new();
+    for (index, line) in lines.into_iter().enumerate() {
+        if index > 0 {
+            contents.push(Content::Character {
+                character: '\n',
+                escaped: false,
+            });
+        }
+        contents.extend(line.into_iter().skip(indentation));
     }
+    contents
 }
 
 #[test]
@@ -35,4 +165,98 @@ fn string_parses() {
             String(std::string::String::from("hello\nworld"))
         ))
     );
+    assert_eq!(
+        test::strip_span(String::parse("\"C:\\\\temp\\t\\x21 \\u{1F600}\"".into())),
+        Ok((
+            std::string::String::new(),
+            String(std::string::String::from("C:\\temp\t! 😀"))
+        ))
+    );
+}
+
+#[test]
+fn invalid_escapes_fail() {
+    let (line, column) = match String::parse("\"first\\n\nsecond \\d\"".into()) {
+        Err(nom::Err::Failure(error)) => {
+            assert_eq!(
+                error.kind,
+                error::ErrorKind::Escape {
+                    sequence: "\\d".to_string()
+                }
+            );
+            assert_eq!(*error.input.fragment(), "\\d\"");
+            (error.input.location_line(), error.input.get_utf8_column())
+        }
+        result => panic!("expected a failure, found {result:?}"),
+    };
+    assert_eq!((line, column), (2, 8));
+}
+
+#[test]
+fn raw_strings_parse() {
+    let parse = |source: &str| {
+        test::strip_span(String::parse(source.into())).map(|(input, string)| (input, string.0))
+    };
+
+    assert_eq!(
+        parse(r#"r"C:\temp\new" rest"#),
+        Ok((" rest".to_string(), r"C:\temp\new".to_string()))
+    );
+    assert_eq!(
+        parse(r#"r"""#),
+        Ok((std::string::String::new(), std::string::String::new()))
+    );
+    assert_eq!(
+        parse("r#\"say \"hi\"\"#"),
+        Ok((std::string::String::new(), r#"say "hi""#.to_string()))
+    );
+    assert_eq!(
+        parse(r###"r##"a "# b"##"###),
+        Ok((std::string::String::new(), r##"a "# b"##.to_string()))
+    );
+    assert_eq!(
+        parse("r\"two\nlines\""),
+        Ok((std::string::String::new(), "two\nlines".to_string()))
+    );
+    // An unterminated raw string fails rather than being parsed as something else
+    assert!(matches!(parse(r#"r#"abc""#), Err(nom::Err::Failure(_))));
+}
+
+#[test]
+fn block_strings_parse() {
+    let parse = |source: &str| {
+        test::strip_span(String::parse(source.into())).map(|(input, string)| (input, string.0))
+    };
+
+    assert_eq!(
+        parse("\"\"\"\n    Dear reader,\n\n      thanks!\n    \"\"\" rest"),
+        Ok((" rest".to_string(), "Dear reader,\n\n  thanks!".to_string()))
+    );
+    // The closing quotes can keep some indentation, or end the last line
+    assert_eq!(
+        parse("\"\"\"\n    a\n      b\n  \"\"\""),
+        Ok((std::string::String::new(), "  a\n    b".to_string()))
+    );
+    assert_eq!(
+        parse("\"\"\"  \r\n\ta\r\n\tb\"\"\""),
+        Ok((std::string::String::new(), "a\nb".to_string()))
+    );
+    // Escapes are still processed, and escaped whitespace is never stripped
+    assert_eq!(
+        parse("\"\"\"\n  \\t\"quoted\"\\n  \\u{41}\n  \"\"\""),
+        Ok((std::string::String::new(), "\t\"quoted\"\n  A".to_string()))
+    );
+    assert_eq!(
+        parse("\"\"\"\n\"\"\""),
+        Ok((std::string::String::new(), std::string::String::new()))
+    );
+
+    assert!(matches!(
+        parse("\"\"\"text\"\"\""),
+        Err(nom::Err::Failure(_))
+    ));
+    assert!(matches!(
+        parse("\"\"\"\n  unterminated"),
+        Err(nom::Err::Failure(_))
+    ));
 }
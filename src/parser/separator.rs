@@ -0,0 +1,22 @@
+use super::prelude::*;
+
+/// Parses the comma between two items of a list, along with any whitespace around it
+pub fn parse(input: Span) -> IResult<Span, ()> {
+    value(
+        (),
+        complete(delimited(
+            whitespace::optional,
+            tag(","),
+            whitespace::optional,
+        )),
+    )(input)
+}
+
+#[test]
+fn separator_parses() {
+    assert_eq!(
+        test::strip_span(parse(" /* comment */ ,\n x".into())),
+        Ok(("x".to_string(), ()))
+    );
+    assert!(parse(" x".into()).is_err());
+}
@@ -1,11 +1,102 @@
 use super::prelude::*;
 
+/// A type annotation, for example `Int64`, `fn(Int64, Int64) -> Boolean`, `[String]`,
+/// `(Int64, Character)` or `Option<Int64>`
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Type<'a>(pub Identifier<'a>);
+pub enum Type<'a> {
+    Named(Identifier<'a>),
+    Function {
+        parameters: Vec<Self>,
+        return_type: Box<Self>,
+    },
+    List(Box<Self>),
+    /// A tuple of any number of types other than one, as a single type in parentheses is just
+    /// that type, unless it is followed by a comma
+    Tuple(Vec<Self>),
+    /// A generic type applied to type arguments
+    Generic {
+        name: Identifier<'a>,
+        arguments: Vec<Self>,
+    },
+}
 
 impl<'a> Type<'a> {
     pub fn parse(input: Span<'a>) -> IResult<Span<'a>, Self> {
-        Identifier::parse.map(Type).parse(input)
+        alt((
+            context("function type", Self::parse_function),
+            context("list type", Self::parse_list),
+            context("tuple type", Self::parse_tuple),
+            context("named type", Self::parse_named),
+        ))(input)
+    }
+
+    /// Parse comma-separated types between `open` and `close`, returning whether there was a
+    /// trailing comma
+    fn parse_list_of(
+        open: &'static str,
+        close: &'static str,
+    ) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, (Vec<Self>, bool)> {
+        delimited(
+            pair(tag(open), whitespace::optional),
+            pair(
+                separated_list0(separator::parse, Self::parse),
+                opt(separator::parse).map(|separator| separator.is_some()),
+            ),
+            pair(whitespace::optional, tag(close)),
+        )
+    }
+
+    fn parse_function(input: Span<'a>) -> IResult<Span<'a>, Self> {
+        let (input, _) = pair(tag("fn"), whitespace::optional)(input)?;
+        let (input, (parameters, _)) = Self::parse_list_of("(", ")")(input)?;
+        let (input, _) = tuple((whitespace::optional, tag("->"), whitespace::optional))(input)?;
+        let (input, return_type) = Self::parse(input)?;
+
+        Ok((
+            input,
+            Self::Function {
+                parameters,
+                return_type: Box::new(return_type),
+            },
+        ))
+    }
+
+    fn parse_list(input: Span<'a>) -> IResult<Span<'a>, Self> {
+        delimited(
+            pair(tag("["), whitespace::optional),
+            Self::parse,
+            pair(whitespace::optional, tag("]")),
+        )
+        .map(|element| Self::List(Box::new(element)))
+        .parse(input)
+    }
+
+    fn parse_tuple(input: Span<'a>) -> IResult<Span<'a>, Self> {
+        Self::parse_list_of("(", ")")
+            .map(|(mut elements, trailing_separator)| {
+                if elements.len() == 1 && !trailing_separator {
+                    elements.remove(0)
+                } else {
+                    Self::Tuple(elements)
+                }
+            })
+            .parse(input)
+    }
+
+    fn parse_named(input: Span<'a>) -> IResult<Span<'a>, Self> {
+        let (input, name) = Identifier::parse(input)?;
+        let (input, arguments) = opt(preceded(
+            whitespace::optional,
+            Self::parse_list_of("<", ">"),
+        ))(input)?;
+
+        Ok((
+            input,
+            match arguments {
+                Some((arguments, _)) => Self::Generic { name, arguments },
+                None => Self::Named(name),
+            },
+        ))
     }
 }
 
@@ -15,6 +106,134 @@ fn type_parses() {
 
     assert_eq!(
         test::strip_span(Type::parse("Int64".into())),
-        Ok((String::new(), Type(Identifier(Span::new("Int64")))))
+        Ok((String::new(), Type::Named(Identifier(Span::new("Int64")))))
+    );
+    assert_eq!(
+        test::strip_span(Type::parse("fnord".into())),
+        Ok((String::new(), Type::Named(Identifier(Span::new("fnord")))))
+    );
+}
+
+#[test]
+fn function_type_parses() {
+    use super::test;
+
+    assert_eq!(
+        test::strip_span(Type::parse("fn(Int64, fn() -> Boolean) -> Int64 x".into())),
+        Ok((
+            " x".to_string(),
+            Type::Function {
+                parameters: vec![
+                    Type::Named(Identifier(unsafe {
+                        Span::new_from_raw_offset(3, 1, "Int64", ())
+                    })),
+                    Type::Function {
+                        parameters: Vec::new(),
+                        return_type: Box::new(Type::Named(Identifier(unsafe {
+                            Span::new_from_raw_offset(18, 1, "Boolean", ())
+                        }))),
+                    },
+                ],
+                return_type: Box::new(Type::Named(Identifier(unsafe {
+                    Span::new_from_raw_offset(30, 1, "Int64", ())
+                }))),
+            }
+        ))
+    );
+
+    // Function types are right associative
+    assert_eq!(
+        Type::parse("fn(A) -> fn(B) -> C".into()).map(|(_, result)| matches!(
+            result,
+            Type::Function { return_type, .. } if matches!(*return_type, Type::Function { .. })
+        )),
+        Ok(true)
+    );
+}
+
+#[test]
+fn compound_types_parse() {
+    use super::test;
+
+    assert_eq!(
+        test::strip_span(Type::parse("[ Int64 ]".into())),
+        Ok((
+            String::new(),
+            Type::List(Box::new(Type::Named(Identifier(unsafe {
+                Span::new_from_raw_offset(2, 1, "Int64", ())
+            }))))
+        ))
+    );
+    assert_eq!(
+        test::strip_span(Type::parse("(Int64, [Boolean],)".into())),
+        Ok((
+            String::new(),
+            Type::Tuple(vec![
+                Type::Named(Identifier(unsafe {
+                    Span::new_from_raw_offset(1, 1, "Int64", ())
+                })),
+                Type::List(Box::new(Type::Named(Identifier(unsafe {
+                    Span::new_from_raw_offset(9, 1, "Boolean", ())
+                }))))
+            ])
+        ))
+    );
+    assert_eq!(
+        test::strip_span(Type::parse("()".into())),
+        Ok((String::new(), Type::Tuple(Vec::new())))
+    );
+    assert_eq!(
+        test::strip_span(Type::parse("(Int64,)".into())),
+        Ok((
+            String::new(),
+            Type::Tuple(vec![Type::Named(Identifier(unsafe {
+                Span::new_from_raw_offset(1, 1, "Int64", ())
+            }))])
+        ))
+    );
+    assert_eq!(
+        test::strip_span(Type::parse("Result<Option<Int64>, String> x".into())),
+        Ok((
+            " x".to_string(),
+            Type::Generic {
+                name: Identifier(Span::new("Result")),
+                arguments: vec![
+                    Type::Generic {
+                        name: Identifier(unsafe { Span::new_from_raw_offset(7, 1, "Option", ()) }),
+                        arguments: vec![Type::Named(Identifier(unsafe {
+                            Span::new_from_raw_offset(14, 1, "Int64", ())
+                        }))]
+                    },
+                    Type::Named(Identifier(unsafe {
+                        Span::new_from_raw_offset(22, 1, "String", ())
+                    }))
+                ]
+            }
+        ))
+    );
+}
+
+#[test]
+fn parenthesized_types_parse() {
+    use super::test;
+
+    assert_eq!(
+        test::strip_span(Type::parse("( Int64 )".into())),
+        Ok((
+            String::new(),
+            Type::Named(Identifier(unsafe {
+                Span::new_from_raw_offset(2, 1, "Int64", ())
+            }))
+        ))
+    );
+
+    // Parentheses group a function type returning a function type on the left of an arrow
+    assert_eq!(
+        Type::parse("fn((fn(A) -> B)) -> C".into()).map(|(_, result)| matches!(
+            result,
+            Type::Function { parameters, .. }
+                if matches!(parameters.as_slice(), [Type::Function { .. }])
+        )),
+        Ok(true)
     );
 }